@@ -43,14 +43,28 @@ def find_localized_name(names: List[dict], lang: str) -> Optional[str]:
     """名前リストから指定言語の名前を取り出す
 
     PokéAPI は言語コードの大文字小文字を変えることがある（ja-Hrkt → ja-hrkt）。
+    空や空白だけの名前は無いものとして飛ばす。
     """
     for name_entry in names:
+        name = name_entry.get('name') or ''
+        if not name.strip():
+            continue
         if name_entry.get('language', {}).get('name', '').lower() == lang:
-            return name_entry.get('name')
+            return name
     return None
 
+def other_names(names: List[dict]) -> Dict[str, str]:
+    """ja-Hrkt と en 以外の名前を、小文字の言語コード → 名前にまとめる（names）"""
+    result = {}
+    for name_entry in names:
+        lang = name_entry.get('language', {}).get('name', '').lower()
+        name = name_entry.get('name') or ''
+        if lang and lang not in ('ja-hrkt', 'en') and name.strip():
+            result[lang] = name
+    return dict(sorted(result.items()))
+
 def get_name_pair(species_data: dict) -> Optional[Dict[str, str]]:
-    """種データから日本語名と英名のペアを抽出"""
+    """種データから日本語名と英名のペア、ほかの言語の名前を抽出"""
     names = species_data.get('names', [])
     pokemon_id = species_data.get('id')
 
@@ -58,13 +72,21 @@ def get_name_pair(species_data: dict) -> Optional[Dict[str, str]]:
     en_name = find_localized_name(names, 'en')
 
     if ja_name and en_name and pokemon_id:
-        return {'ja': ja_name, 'en': en_name, 'id': pokemon_id}
+        # 種は pokemon-species のスラッグ（デフォルト個体の deoxys-normal ではなく deoxys）
+        entry = {'ja': ja_name, 'en': en_name, 'id': pokemon_id, 'slug': species_data['name']}
+        localized = other_names(names)
+        if localized:
+            entry['names'] = localized
+        entry['species_id'] = pokemon_id
+        entry['is_default'] = True
+        return entry
     return None
 
-def get_variety_refs(species_data: dict, base_ja: str) -> List[Tuple[str, str]]:
-    """種データから非デフォルトの個体（フォルム）URLと種の日本語名を返す"""
+def get_variety_refs(species_data: dict, base_ja: str,
+                     base_names: Dict[str, str]) -> List[Tuple[int, str, Dict[str, str], str]]:
+    """種データから非デフォルトの個体（フォルム）URLと種の id・日本語名・ほかの言語の名前を返す"""
     return [
-        (base_ja, v['pokemon']['url'])
+        (species_data['id'], base_ja, base_names, v['pokemon']['url'])
         for v in species_data.get('varieties', [])
         if not v.get('is_default')
     ]
@@ -80,6 +102,14 @@ def compose_ja(base_ja: str, form_ja: str) -> str:
         return form_ja
     return f'{base_ja}（{form_ja}）'
 
+def compose_localized(lang: str, base: str, form: str) -> str:
+    """compose_ja のほかの言語版。漢字圏は全角括弧、それ以外は半角括弧で合成する"""
+    if lang in ('ja', 'zh-hans', 'zh-hant'):
+        return compose_ja(base, form)
+    if base in form:
+        return form
+    return f'{base} ({form})'
+
 def slug_to_en(slug: str) -> str:
     """英名を持たないフォルム（コライドン/ミライドンの各ビルド）用のフォールバック"""
     return ' '.join(part.capitalize() for part in slug.split('-'))
@@ -89,7 +119,21 @@ def extract_types(pokemon_data: dict) -> List[str]:
     slots = sorted(pokemon_data.get('types', []), key=lambda t: t['slot'])
     return [t['type']['name'] for t in slots]
 
-def fetch_form_entry(base_ja: str, pokemon_url: str) -> Optional[Dict[str, str]]:
+def form_category(form_slug: str) -> str:
+    """フォルムのスラッグ（種名を除いた部分）から種類を決める（models.rs の FormCategory）"""
+    tokens = form_slug.split('-')
+    if 'mega' in tokens:
+        return 'mega'
+    if 'gmax' in tokens:
+        return 'gmax'
+    if any(t in ('alola', 'galar', 'hisui', 'paldea') for t in tokens):
+        return 'regional'
+    if 'primal' in tokens:
+        return 'primal'
+    return 'other'
+
+def fetch_form_entry(base_id: int, base_ja: str, base_names: Dict[str, str],
+                     pokemon_url: str) -> Optional[Dict[str, str]]:
     """個体URLからフォルムのエントリを作る
 
     pokemon と pokemon-form は id 体系が別なので、pokemon 経由で form を辿る。
@@ -107,7 +151,18 @@ def fetch_form_entry(base_ja: str, pokemon_url: str) -> Optional[Dict[str, str]]
 
     form_en = find_localized_name(form_data.get('names', []), 'en')
 
-    return {
+    # ほかの言語も en と同じく names の完成した名前を優先し、
+    # 無ければ種名と form_names の呼称から組み立てる
+    names = {}
+    for lang, base in base_names.items():
+        full = find_localized_name(form_data.get('names', []), lang)
+        label = find_localized_name(form_data.get('form_names', []), lang)
+        if full:
+            names[lang] = full
+        elif label:
+            names[lang] = compose_localized(lang, base, label)
+
+    entry = {
         'ja': compose_ja(base_ja, form_ja),
         'en': form_en or slug_to_en(pokemon_data['name']),
         'id': pokemon_data['id'],
@@ -115,6 +170,15 @@ def fetch_form_entry(base_ja: str, pokemon_url: str) -> Optional[Dict[str, str]]
         'slug': pokemon_data['name'],
         'species_slug': pokemon_data['species']['name'],
     }
+    if names:
+        entry['names'] = names
+    # 種名を除いたスラッグ（alola / mega-x など）。分類もここから決める
+    form_slug = '-'.join(form_tokens(entry))
+    entry['species_id'] = base_id
+    entry['form_slug'] = form_slug
+    entry['is_default'] = False
+    entry['form_category'] = form_category(form_slug)
+    return entry
 
 def dedupe_en(entries: List[Dict[str, str]]) -> None:
     """英名が衝突するフォルムをスラッグ由来の名前に置き換える
@@ -128,7 +192,7 @@ def dedupe_en(entries: List[Dict[str, str]]) -> None:
     }
 
     for entry in entries:
-        if entry['en'] in duplicates and 'slug' in entry:
+        if entry['en'] in duplicates and 'species_slug' in entry:
             entry['en'] = slug_to_en(entry['slug'])
 
 def form_tokens(entry: Dict[str, str]) -> List[str]:
@@ -144,6 +208,7 @@ def disambiguate_ja(entries: List[Dict[str, str]]) -> None:
     （search.rs の HashMap）、同名のままでは選び分けられない。
     識別子はグループ内で差分になるトークンだけを使う（Orange / Combat など）。
     種のエントリは書き換えず、フォルム側だけを変える。
+    漢字まじりの表記（names の ja）にも同じ識別子を付ける。
     """
     groups = defaultdict(list)
     for entry in entries:
@@ -153,7 +218,7 @@ def disambiguate_ja(entries: List[Dict[str, str]]) -> None:
         if len(group) < 2:
             continue
 
-        forms = [e for e in group if 'slug' in e]
+        forms = [e for e in group if 'species_slug' in e]
         token_lists = [form_tokens(e) for e in forms]
         if not token_lists:
             continue
@@ -162,11 +227,19 @@ def disambiguate_ja(entries: List[Dict[str, str]]) -> None:
         for entry, tokens in zip(forms, token_lists):
             distinct = [t for t in tokens if t not in shared] or tokens
             label = ' '.join(t.capitalize() for t in distinct)
-            # 「ロコン（アローラのすがた）」のように既に括弧付きなら中に足す
-            if entry['ja'].endswith('）'):
-                entry['ja'] = f'{entry["ja"][:-1]}・{label}）'
-            else:
-                entry['ja'] = f'{entry["ja"]}（{label}）'
+            entry['ja'] = append_label(entry['ja'], label)
+            names = entry.get('names', {})
+            if 'ja' in names:
+                names['ja'] = append_label(names['ja'], label)
+
+def append_label(ja: str, label: str) -> str:
+    """日本語名に識別子を足す
+
+    「ロコン（アローラのすがた）」のように既に括弧付きなら中に足す。
+    """
+    if ja.endswith('）'):
+        return f'{ja[:-1]}・{label}）'
+    return f'{ja}（{label}）'
 
 def main():
     output_file = sys.argv[1] if len(sys.argv) > 1 else 'names.json'
@@ -216,7 +289,8 @@ def main():
                 if default_url:
                     name_pair['types'] = extract_types(fetch_json(default_url))
                 entries.append(name_pair)
-                variety_refs.extend(get_variety_refs(species_data, name_pair['ja']))
+                variety_refs.extend(get_variety_refs(
+                    species_data, name_pair['ja'], name_pair.get('names', {})))
         except Exception as e:
             print(f'Error: Failed to process {species_ref["name"]}: {e}', file=sys.stderr)
             error_count += 1
@@ -227,9 +301,9 @@ def main():
     print(f'\nProcessing {len(variety_refs)} forms...', file=sys.stderr)
 
     def process_variety(ref):
-        base_ja, pokemon_url = ref
+        base_id, base_ja, base_names, pokemon_url = ref
         try:
-            return fetch_form_entry(base_ja, pokemon_url)
+            return fetch_form_entry(base_id, base_ja, base_names, pokemon_url)
         except Exception as e:
             print(f'Error: Failed to process form {pokemon_url}: {e}', file=sys.stderr)
             return e
@@ -247,8 +321,8 @@ def main():
 
     dedupe_en(entries)
     disambiguate_ja(entries)
+    # slug は --output slug / showdown 用に残す。species_slug はフォルムの目印にしか使わない
     for entry in form_entries:
-        del entry['slug']
         del entry['species_slug']
 
     # 日本語名は検索キー、英名は検索結果の出力値かつスプライト取得のキーなので
@@ -266,7 +340,7 @@ def main():
     print('\nGenerating final JSON...', file=sys.stderr)
 
     output = {
-        'schema_version': 2,
+        'schema_version': 3,
         'generated_at': datetime.now(timezone.utc).strftime('%Y-%m-%dT%H:%M:%SZ'),
         'count': len(entries),
         'entries': entries
@@ -0,0 +1,203 @@
+//! 1行1件の名前リストをまとめて変換する（--batch）。
+//!
+//! 1プロセス1件だと names.json を毎回読み直すため、パーティ表のような
+//! 数百行のリストでは遅い。辞書は1回だけ読み、各行を完全一致で引く。
+//! 対話選択（skim）は決して開かない。
+
+use crate::models::NameEntry;
+use crate::output::{NameStyle, OutputFormat};
+use anyhow::{Context, Result};
+use std::io::{BufRead, Write};
+
+/// 変換できなかった1行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchFailure {
+    /// 1始まりの行番号
+    pub line_no: usize,
+    pub input: String,
+}
+
+/// バッチ変換の集計
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    /// 空行を除いた処理件数
+    pub total: usize,
+    pub failures: Vec<BatchFailure>,
+}
+
+impl BatchReport {
+    /// 全件変換できたら 0、1件でも失敗があれば 2（単発検索の「候補なし」と揃える）。
+    /// 件数は表さない。何件失敗したかは summary の行で伝える
+    pub fn exit_code(&self) -> i32 {
+        if self.failures.is_empty() { 0 } else { 2 }
+    }
+
+    /// 最後に標準エラーへ出す集計行（N of M lines not found）。全件変換できたら None
+    pub fn summary(&self) -> Option<String> {
+        if self.failures.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "{} of {} lines not found",
+            self.failures.len(),
+            self.total
+        ))
+    }
+}
+
+/// 各行を `lookup` で引き、結果を入力と同じ順で `out` に書く。
+///
+/// text 形式では出力の行を入力の行と1対1に対応させる。引けなかった行と空行は
+/// 空行を出すので、表計算ソフトに貼り戻しても行がずれない。構造化形式は
+/// query 列で対応が取れるので空行は飛ばす。前後の空白は取り除いてから引く。
+pub fn translate_lines<'a, R, W, F>(
+    reader: R,
+    out: &mut W,
+    format: OutputFormat,
+    style: NameStyle,
+    lookup: F,
+) -> Result<BatchReport>
+where
+    R: BufRead,
+    W: Write,
+    F: Fn(&str) -> Option<&'a NameEntry>,
+{
+    let mut report = BatchReport::default();
+
+    if let Some(header) = format.header() {
+        writeln!(out, "{}", header)?;
+    }
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.context("Failed to read input line")?;
+        let name = line.trim();
+
+        if name.is_empty() {
+            if format.is_text() {
+                writeln!(out)?;
+            }
+            continue;
+        }
+
+        report.total += 1;
+        let entry = lookup(name);
+        match format.record(name, entry, style) {
+            Some(record) => writeln!(out, "{}", record)?,
+            None => writeln!(out)?,
+        }
+        if entry.is_none() {
+            report.failures.push(BatchFailure {
+                line_no: i + 1,
+                input: name.to_string(),
+            });
+        }
+    }
+
+    out.flush()?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language;
+    use std::io::Cursor;
+
+    fn entries() -> Vec<NameEntry> {
+        vec![
+            NameEntry {
+                ja: "ピカチュウ".to_string(),
+                en: "Pikachu".to_string(),
+                id: Some(25),
+                types: vec!["electric".to_string()],
+                ..Default::default()
+            },
+            NameEntry {
+                ja: "フシギダネ".to_string(),
+                en: "Bulbasaur".to_string(),
+                id: Some(1),
+                types: vec![],
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn lookup<'a>(entries: &'a [NameEntry]) -> impl Fn(&str) -> Option<&'a NameEntry> {
+        move |name| entries.iter().find(|e| e.ja == name)
+    }
+
+    #[test]
+    fn test_translate_lines_keeps_order_and_alignment() {
+        let input = Cursor::new("ピカチュウ\nミュウツー\n\n  フシギダネ  \n");
+        let mut out = Vec::new();
+        let entries = entries();
+
+        let report = translate_lines(
+            input,
+            &mut out,
+            OutputFormat::Text,
+            NameStyle::new(Language::En, None),
+            lookup(&entries),
+        )
+        .unwrap();
+
+        // 失敗行と空行は空行で埋め、入力と行を揃える
+        assert_eq!(String::from_utf8(out).unwrap(), "Pikachu\n\n\nBulbasaur\n");
+        assert_eq!(report.total, 3);
+        assert_eq!(
+            report.failures,
+            vec![BatchFailure {
+                line_no: 2,
+                input: "ミュウツー".to_string()
+            }]
+        );
+        assert_eq!(report.exit_code(), 2);
+        assert_eq!(report.summary().as_deref(), Some("1 of 3 lines not found"));
+    }
+
+    #[test]
+    fn test_translate_lines_all_resolved() {
+        let input = Cursor::new("フシギダネ\r\nピカチュウ");
+        let mut out = Vec::new();
+        let entries = entries();
+
+        let report = translate_lines(
+            input,
+            &mut out,
+            OutputFormat::Text,
+            NameStyle::new(Language::En, None),
+            lookup(&entries),
+        )
+        .unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "Bulbasaur\nPikachu\n");
+        assert!(report.failures.is_empty());
+        assert_eq!(report.exit_code(), 0);
+        assert_eq!(report.summary(), None);
+    }
+
+    #[test]
+    fn test_translate_lines_tsv_skips_blank_lines_and_reports_missing() {
+        let input = Cursor::new("ピカチュウ\n\nミュウツー\n");
+        let mut out = Vec::new();
+        let entries = entries();
+
+        let report = translate_lines(
+            input,
+            &mut out,
+            OutputFormat::Tsv,
+            NameStyle::new(Language::En, None),
+            lookup(&entries),
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "query\tstatus\tja\ten\tid\ttypes\n\
+             ピカチュウ\tok\tピカチュウ\tPikachu\t25\telectric\n\
+             ミュウツー\tnot_found\t\t\t\t\n"
+        );
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].line_no, 3);
+    }
+}
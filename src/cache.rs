@@ -0,0 +1,450 @@
+//! スプライト・鳴き声・情報のキャッシュ管理（cache サブコマンド）。
+//!
+//! データディレクトリ直下の構成:
+//!
+//! - `sprites/{id}.png`: 通常のスプライト。ほかの種類は `sprites/shiny/{id}.png` など、
+//!   アニメーションは `sprites/animated/{id}.gif`（種類ごとに同じサブディレクトリ）
+//! - `cries/{id}.ogg`: 鳴き声
+//! - `info/{id}.json`: タイプ・種族値・図鑑説明など
+//! - `info/{ability,item,move}/{slug}.json`: 特性・道具・わざの日本語名
+//! - `info/{evolution-chain,learnset}/{id}.json`: 進化の系統・覚えるわざ
+//!   （id は系統の id・ポケモンの id で、辞書の id とは照合しない）
+//!
+//! 件数と容量の確認、壊れたファイルの検出、辞書に無くなった id と
+//! 取得途中の `.part` の掃除、種類ごとの削除を行う。
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use directories::ProjectDirs;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// キャッシュの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CacheKind {
+    /// スプライト画像
+    Sprites,
+    /// 鳴き声
+    Cries,
+    /// タイプ・種族値・図鑑説明・特性名
+    Info,
+}
+
+impl CacheKind {
+    pub const ALL: [CacheKind; 3] = [CacheKind::Sprites, CacheKind::Cries, CacheKind::Info];
+
+    /// データディレクトリ下のディレクトリ名。表示名も兼ねる
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            CacheKind::Sprites => "sprites",
+            CacheKind::Cries => "cries",
+            CacheKind::Info => "info",
+        }
+    }
+
+    /// キャッシュファイルの拡張子。スプライトはアニメーション GIF も置く
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            CacheKind::Sprites => &["png", "gif"],
+            CacheKind::Cries => &["ogg"],
+            CacheKind::Info => &["json"],
+        }
+    }
+}
+
+/// キャッシュディレクトリ内の1ファイルの分類
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CacheFile {
+    /// `{id}.{拡張子}`
+    Entry(u32),
+    /// 情報のサブディレクトリにある json（特性などの日本語名・進化の系統・覚えるわざ）。
+    /// 名前が数字でも（`evolution-chain/10.json`）pokemon の id ではないので、
+    /// 検証・掃除の対象にしない
+    Named,
+    /// 取得途中で残った一時ファイル（`.part` を含む）
+    Partial,
+    /// それ以外。作った覚えが無いので触らない
+    Other,
+}
+
+/// `nested` はキャッシュディレクトリ直下ではなくサブディレクトリにあるか
+fn classify(kind: CacheKind, file_name: &str, nested: bool) -> CacheFile {
+    if file_name.contains(".part") {
+        return CacheFile::Partial;
+    }
+    // 情報のサブディレクトリは CachedInfo ではないので、id として読まない
+    if kind == CacheKind::Info && nested {
+        return if file_name.ends_with(".json") {
+            CacheFile::Named
+        } else {
+            CacheFile::Other
+        };
+    }
+    let entry = kind
+        .extensions()
+        .iter()
+        .find_map(|extension| {
+            file_name
+                .strip_suffix(extension)
+                .and_then(|stem| stem.strip_suffix('.'))
+                .and_then(|id| id.parse().ok())
+        })
+        .map(CacheFile::Entry);
+    entry.unwrap_or(CacheFile::Other)
+}
+
+/// 1種類分の件数と容量
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub bytes: u64,
+    /// 残っている `.part` の数
+    pub partials: usize,
+}
+
+/// キャッシュ全体（データディレクトリ）
+pub struct CacheStore {
+    root: PathBuf,
+}
+
+impl CacheStore {
+    pub fn new() -> Result<Self> {
+        let project_dirs = ProjectDirs::from("", "", "poke-lookup")
+            .or_else(|| ProjectDirs::from("dev", "poke-lookup", "poke-lookup"))
+            .context("Failed to determine project directories")?;
+        Ok(Self::with_root(project_dirs.data_dir().to_path_buf()))
+    }
+
+    pub fn with_root(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn dir(&self, kind: CacheKind) -> PathBuf {
+        self.root.join(kind.dir_name())
+    }
+
+    /// ディレクトリ内（サブディレクトリ含む）のファイルと分類。ディレクトリが無ければ空
+    fn files(&self, kind: CacheKind) -> Result<Vec<(PathBuf, CacheFile)>> {
+        let dir = self.dir(kind);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = Vec::new();
+        collect_files(kind, &dir, false, &mut files)?;
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(files)
+    }
+
+    pub fn stats(&self, kind: CacheKind) -> Result<CacheStats> {
+        let mut stats = CacheStats::default();
+        for (path, class) in self.files(kind)? {
+            match class {
+                CacheFile::Entry(_) | CacheFile::Named => {
+                    stats.entries += 1;
+                    stats.bytes += std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                }
+                CacheFile::Partial => stats.partials += 1,
+                CacheFile::Other => {}
+            }
+        }
+        Ok(stats)
+    }
+
+    /// `is_valid` で読めないファイルを返す。`delete` なら消す
+    pub fn verify<F>(&self, kind: CacheKind, is_valid: F, delete: bool) -> Result<Vec<PathBuf>>
+    where
+        F: Fn(&Path) -> bool,
+    {
+        let corrupt: Vec<PathBuf> = self
+            .files(kind)?
+            .into_iter()
+            .filter(|(path, class)| matches!(class, CacheFile::Entry(_)) && !is_valid(path))
+            .map(|(path, _)| path)
+            .collect();
+        if delete {
+            remove_all(&corrupt)?;
+        }
+        Ok(corrupt)
+    }
+
+    /// 辞書に無い id のファイルと `.part` を返す。`dry_run` でなければ消す
+    pub fn prune(
+        &self,
+        kind: CacheKind,
+        known_ids: &HashSet<u32>,
+        dry_run: bool,
+    ) -> Result<Vec<PathBuf>> {
+        let targets: Vec<PathBuf> = self
+            .files(kind)?
+            .into_iter()
+            .filter(|(_, class)| match class {
+                CacheFile::Entry(id) => !known_ids.contains(id),
+                CacheFile::Partial => true,
+                CacheFile::Named | CacheFile::Other => false,
+            })
+            .map(|(path, _)| path)
+            .collect();
+        if !dry_run {
+            remove_all(&targets)?;
+        }
+        Ok(targets)
+    }
+
+    /// キャッシュのファイル（`.part` 含む）をすべて消し、消した数を返す。
+    /// ディレクトリ自体と見覚えの無いファイルは残す
+    pub fn clear(&self, kind: CacheKind) -> Result<usize> {
+        let targets: Vec<PathBuf> = self
+            .files(kind)?
+            .into_iter()
+            .filter(|(_, class)| *class != CacheFile::Other)
+            .map(|(path, _)| path)
+            .collect();
+        remove_all(&targets)?;
+        Ok(targets.len())
+    }
+}
+
+fn collect_files(
+    kind: CacheKind,
+    dir: &Path,
+    nested: bool,
+    files: &mut Vec<(PathBuf, CacheFile)>,
+) -> Result<()> {
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read cache directory: {}", dir.display()))?
+    {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(kind, &entry.path(), true, files)?;
+        } else if file_type.is_file() {
+            let class = classify(kind, &entry.file_name().to_string_lossy(), nested);
+            files.push((entry.path(), class));
+        }
+    }
+    Ok(())
+}
+
+fn remove_all(paths: &[PathBuf]) -> Result<()> {
+    for path in paths {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// バイト数を 1024 単位で読みやすく（12.3 MB など）
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn create_store() -> (tempfile::TempDir, CacheStore) {
+        let temp_dir = tempdir().unwrap();
+        let store = CacheStore::with_root(temp_dir.path().to_path_buf());
+        let sprites = store.dir(CacheKind::Sprites);
+        fs::create_dir_all(&sprites).unwrap();
+        fs::write(sprites.join("1.png"), b"aaaa").unwrap();
+        fs::write(sprites.join("25.png"), b"bb").unwrap();
+        fs::write(sprites.join("99999.png"), b"c").unwrap();
+        fs::write(sprites.join("25.png.1234.part"), b"partial").unwrap();
+        fs::write(sprites.join("README.txt"), b"keep").unwrap();
+        (temp_dir, store)
+    }
+
+    fn names(paths: &[PathBuf]) -> Vec<String> {
+        paths
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            classify(CacheKind::Sprites, "25.png", false),
+            CacheFile::Entry(25)
+        );
+        assert_eq!(
+            classify(CacheKind::Sprites, "25.gif", false),
+            CacheFile::Entry(25)
+        );
+        assert_eq!(
+            classify(CacheKind::Cries, "25.ogg", false),
+            CacheFile::Entry(25)
+        );
+        assert_eq!(
+            classify(CacheKind::Cries, "25.ogg.4321.part", false),
+            CacheFile::Partial
+        );
+        assert_eq!(
+            classify(CacheKind::Sprites, "25.ogg", false),
+            CacheFile::Other
+        );
+        assert_eq!(
+            classify(CacheKind::Info, "abc.json", false),
+            CacheFile::Other
+        );
+        assert_eq!(
+            classify(CacheKind::Info, "static.json", true),
+            CacheFile::Named
+        );
+        assert_eq!(classify(CacheKind::Info, "10.json", true), CacheFile::Named);
+        assert_eq!(
+            classify(CacheKind::Info, "25.json", false),
+            CacheFile::Entry(25)
+        );
+        assert_eq!(
+            classify(CacheKind::Sprites, "abc.png", true),
+            CacheFile::Other
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let (_temp_dir, store) = create_store();
+        assert_eq!(
+            store.stats(CacheKind::Sprites).unwrap(),
+            CacheStats {
+                entries: 3,
+                bytes: 7,
+                partials: 1,
+            }
+        );
+        // ディレクトリが無い種類は空
+        assert_eq!(
+            store.stats(CacheKind::Cries).unwrap(),
+            CacheStats::default()
+        );
+    }
+
+    #[test]
+    fn test_verify_reports_and_deletes_corrupt() {
+        let (_temp_dir, store) = create_store();
+        let is_valid = |path: &Path| fs::read(path).unwrap().len() > 1;
+
+        let corrupt = store.verify(CacheKind::Sprites, is_valid, false).unwrap();
+        assert_eq!(names(&corrupt), ["99999.png"]);
+        assert!(corrupt[0].exists());
+
+        store.verify(CacheKind::Sprites, is_valid, true).unwrap();
+        assert!(!corrupt[0].exists());
+    }
+
+    #[test]
+    fn test_prune_removes_orphans_and_partials() {
+        let (_temp_dir, store) = create_store();
+        let known: HashSet<u32> = [1, 25].into_iter().collect();
+
+        let dry = store.prune(CacheKind::Sprites, &known, true).unwrap();
+        assert_eq!(names(&dry), ["25.png.1234.part", "99999.png"]);
+        assert!(dry.iter().all(|p| p.exists()));
+
+        store.prune(CacheKind::Sprites, &known, false).unwrap();
+        let remaining = store.dir(CacheKind::Sprites);
+        assert!(remaining.join("1.png").exists());
+        assert!(remaining.join("25.png").exists());
+        assert!(!remaining.join("99999.png").exists());
+        assert!(!remaining.join("25.png.1234.part").exists());
+    }
+
+    #[test]
+    fn test_clear_keeps_unknown_files() {
+        let (_temp_dir, store) = create_store();
+        assert_eq!(store.clear(CacheKind::Sprites).unwrap(), 4);
+        assert!(store.dir(CacheKind::Sprites).join("README.txt").exists());
+        assert_eq!(
+            store.stats(CacheKind::Sprites).unwrap(),
+            CacheStats::default()
+        );
+    }
+
+    #[test]
+    fn test_variant_subdirectories_are_included() {
+        let (_temp_dir, store) = create_store();
+        let shiny = store.dir(CacheKind::Sprites).join("shiny");
+        fs::create_dir_all(&shiny).unwrap();
+        fs::write(shiny.join("25.png"), b"ddd").unwrap();
+        fs::write(shiny.join("99999.png"), b"e").unwrap();
+
+        let stats = store.stats(CacheKind::Sprites).unwrap();
+        assert_eq!(stats.entries, 5);
+        assert_eq!(stats.bytes, 11);
+
+        let known: HashSet<u32> = [1, 25].into_iter().collect();
+        let orphans = store.prune(CacheKind::Sprites, &known, false).unwrap();
+        assert!(orphans.contains(&shiny.join("99999.png")));
+        assert!(shiny.join("25.png").exists());
+    }
+
+    #[test]
+    fn test_named_info_files_are_kept_by_prune() {
+        let temp_dir = tempdir().unwrap();
+        let store = CacheStore::with_root(temp_dir.path().to_path_buf());
+        let abilities = store.dir(CacheKind::Info).join("ability");
+        fs::create_dir_all(&abilities).unwrap();
+        fs::write(abilities.join("static.json"), b"{}").unwrap();
+
+        assert_eq!(store.stats(CacheKind::Info).unwrap().entries, 1);
+        let known = HashSet::new();
+        assert!(
+            store
+                .prune(CacheKind::Info, &known, false)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(store.clear(CacheKind::Info).unwrap(), 1);
+        assert!(!abilities.join("static.json").exists());
+    }
+
+    #[test]
+    fn test_verify_skips_nested_numeric_info_files() {
+        let temp_dir = tempdir().unwrap();
+        let store = CacheStore::with_root(temp_dir.path().to_path_buf());
+        let info = store.dir(CacheKind::Info);
+        let chains = info.join("evolution-chain");
+        fs::create_dir_all(&chains).unwrap();
+        fs::write(info.join("25.json"), b"broken").unwrap();
+        fs::write(chains.join("10.json"), b"{\"chain\":{}}").unwrap();
+
+        // 進化の系統は CachedInfo として読めないが、id のファイルではないので検証しない
+        let is_valid = |path: &Path| fs::read(path).unwrap() != b"broken";
+        let corrupt = store.verify(CacheKind::Info, is_valid, true).unwrap();
+        assert_eq!(corrupt, [info.join("25.json")]);
+        assert!(chains.join("10.json").exists());
+
+        // 掃除でも進化の系統の id を pokemon の id として扱わない
+        let known = HashSet::new();
+        assert!(
+            store
+                .prune(CacheKind::Info, &known, false)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(chains.join("10.json").exists());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.0 KB");
+        assert_eq!(format_size(5 * 1024 * 1024 + 512 * 1024), "5.5 MB");
+    }
+}
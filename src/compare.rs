@@ -0,0 +1,313 @@
+//! 複数のポケモンを横に並べて比べる（compare サブコマンド）。
+//!
+//! スプライト・名前・タイプを列ごとに並べ、種族値は行を揃えたゲージに
+//! 1列目との差を添える。フォルム違いや進化前後の見比べに使う。
+//! 文字列を組み立てるだけの純粋関数で、取得は呼び出し側で済ませておく。
+
+use crate::info::{self, PokemonInfo};
+
+/// 1列の表示幅（文字数）。ゲージ行「  値 棒」＋差分が収まる幅
+pub const COLUMN_WIDTH: usize = 36;
+
+/// スプライトの最大の大きさ（文字数）。列の幅から余白を引いたもの
+pub const SPRITE_WIDTH: u32 = COLUMN_WIDTH as u32 - 4;
+pub const SPRITE_HEIGHT: u32 = 16;
+
+/// ラベル列の幅（info のラベルは表示幅8桁に揃え済み）
+const LABEL_WIDTH: usize = 8;
+
+/// 比べる1匹分
+pub struct CompareColumn {
+    pub id: Option<u32>,
+    pub japanese: String,
+    pub english: String,
+    /// 描画済みのスプライト。取得できなければ空
+    pub sprite: Vec<String>,
+    /// 取得できなければ None（名前だけ並べる）
+    pub info: Option<PokemonInfo>,
+}
+
+/// 比較表全体。1列目を基準に種族値の差を出す
+pub fn format_comparison(columns: &[CompareColumn]) -> String {
+    let mut rows: Vec<(String, Vec<String>)> = Vec::new();
+
+    // スプライトは下端を揃える（足元が同じ高さに来る）
+    let sprite_height = columns.iter().map(|c| c.sprite.len()).max().unwrap_or(0);
+    for row in 0..sprite_height {
+        let cells = columns
+            .iter()
+            .map(|column| {
+                let offset = sprite_height - column.sprite.len();
+                row.checked_sub(offset)
+                    .and_then(|i| column.sprite.get(i))
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .collect();
+        rows.push((String::new(), cells));
+    }
+
+    rows.push((
+        String::new(),
+        columns
+            .iter()
+            .map(|column| match column.id {
+                Some(id) => format!("No.{}  {}", id, column.japanese),
+                None => column.japanese.clone(),
+            })
+            .collect(),
+    ));
+    rows.push((
+        String::new(),
+        columns
+            .iter()
+            .map(|column| column.english.clone())
+            .collect(),
+    ));
+    rows.push((
+        String::new(),
+        columns
+            .iter()
+            .map(|column| {
+                column
+                    .info
+                    .as_ref()
+                    .map(|info| {
+                        info::format_types(&info.types)
+                            .trim_matches('\n')
+                            .to_string()
+                    })
+                    .unwrap_or_default()
+            })
+            .collect(),
+    ));
+
+    let base = columns.first().and_then(|c| c.info.as_ref());
+    if let Some(labels) = stat_labels(columns) {
+        rows.push((String::new(), vec![String::new(); columns.len()]));
+        for label in labels {
+            let base_value = base.and_then(|info| stat_value(info, label));
+            let cells = columns
+                .iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    let value = column
+                        .info
+                        .as_ref()
+                        .and_then(|info| stat_value(info, label));
+                    match value {
+                        Some(value) => {
+                            let gauge = info::format_stat_gauge("", value);
+                            match base_value {
+                                Some(base_value) if i > 0 => {
+                                    format!("{} {}", gauge, format_delta(value, base_value))
+                                }
+                                _ => gauge,
+                            }
+                        }
+                        None => String::new(),
+                    }
+                })
+                .collect();
+            rows.push((label.to_string(), cells));
+        }
+
+        let base_total = base.map(total);
+        let cells = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| match column.info.as_ref().map(total) {
+                Some(value) => match base_total {
+                    Some(base_total) if i > 0 => {
+                        format!("  {:>3} {}", value, format_delta(value, base_total))
+                    }
+                    _ => format!("  {:>3}", value),
+                },
+                None => String::new(),
+            })
+            .collect();
+        rows.push(("ごうけい".to_string(), cells));
+    }
+
+    let mut out = String::new();
+    for (label, cells) in rows {
+        let mut line = pad(&label, LABEL_WIDTH);
+        for cell in &cells {
+            line.push_str(&pad(cell, COLUMN_WIDTH));
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// 種族値の行に使うラベル。種族値が取れた最初の1匹の並び（どれも同じ順）
+fn stat_labels(columns: &[CompareColumn]) -> Option<Vec<&'static str>> {
+    columns
+        .iter()
+        .filter_map(|c| c.info.as_ref())
+        .find(|info| !info.stats.is_empty())
+        .map(|info| info.stats.iter().map(|stat| stat.label).collect())
+}
+
+fn stat_value(info: &PokemonInfo, label: &str) -> Option<u16> {
+    info.stats
+        .iter()
+        .find(|stat| stat.label == label)
+        .map(|stat| stat.value)
+}
+
+fn total(info: &PokemonInfo) -> u16 {
+    info.stats.iter().map(|stat| stat.value).sum()
+}
+
+/// 基準との差。同じなら ±0
+fn format_delta(value: u16, base: u16) -> String {
+    match value.cmp(&base) {
+        std::cmp::Ordering::Greater => format!("(+{})", value - base),
+        std::cmp::Ordering::Less => format!("(-{})", base - value),
+        std::cmp::Ordering::Equal => "(±0)".to_string(),
+    }
+}
+
+/// 表示幅が width になるまで空白を足す（はみ出す分はそのまま）
+fn pad(text: &str, width: usize) -> String {
+    let visible = visible_width(text);
+    format!("{}{}", text, " ".repeat(width.saturating_sub(visible)))
+}
+
+/// 端末上の表示幅。SGR のエスケープは数えず、かな・漢字・全角記号は2桁とする
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // ESC [ ... m を読み飛ばす
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+            continue;
+        }
+        width += if is_wide(c) { 2 } else { 1 };
+    }
+    width
+}
+
+fn is_wide(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x115F      // ハングル字母
+        | 0x2E80..=0x303E    // CJK 部首・記号と句読点
+        | 0x3041..=0x33FF    // かな・CJK 互換
+        | 0x3400..=0x4DBF    // CJK 拡張A
+        | 0x4E00..=0x9FFF    // CJK 統合漢字
+        | 0xAC00..=0xD7A3    // ハングル音節
+        | 0xF900..=0xFAFF    // CJK 互換漢字
+        | 0xFF01..=0xFF60    // 全角英数・記号
+        | 0xFFE0..=0xFFE6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info::{PokemonType, StatEntry};
+    use crate::render::strip_sgr;
+
+    fn column(japanese: &str, english: &str, stats: &[(&'static str, u16)]) -> CompareColumn {
+        CompareColumn {
+            id: None,
+            japanese: japanese.to_string(),
+            english: english.to_string(),
+            sprite: Vec::new(),
+            info: Some(PokemonInfo {
+                types: vec![PokemonType {
+                    slug: "electric".to_string(),
+                    ja: "でんき".to_string(),
+                    color: "",
+                }],
+                stats: stats
+                    .iter()
+                    .map(|&(label, value)| StatEntry { label, value })
+                    .collect(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_visible_width() {
+        assert_eq!(visible_width("abc"), 3);
+        assert_eq!(visible_width("ピカチュウ"), 10);
+        assert_eq!(visible_width("\x1b[38;5;220m███\x1b[0m"), 3);
+    }
+
+    #[test]
+    fn test_format_delta() {
+        assert_eq!(format_delta(60, 35), "(+25)");
+        assert_eq!(format_delta(35, 60), "(-25)");
+        assert_eq!(format_delta(50, 50), "(±0)");
+    }
+
+    #[test]
+    fn test_format_comparison_aligns_columns_and_shows_deltas() {
+        let columns = [
+            column(
+                "ピカチュウ",
+                "Pikachu",
+                &[("HP      ", 35), ("すばやさ", 90)],
+            ),
+            column(
+                "ライチュウ",
+                "Raichu",
+                &[("HP      ", 60), ("すばやさ", 110)],
+            ),
+        ];
+        let out = strip_sgr(&format_comparison(&columns));
+        let lines: Vec<&str> = out.lines().collect();
+
+        // 名前の行: 2列目は LABEL_WIDTH + COLUMN_WIDTH 桁目から始まる
+        let names = lines
+            .iter()
+            .find(|line| line.contains("ピカチュウ"))
+            .unwrap();
+        let second = names.find("ライチュウ").unwrap();
+        assert_eq!(visible_width(&names[..second]), LABEL_WIDTH + COLUMN_WIDTH);
+
+        let hp = lines.iter().find(|line| line.starts_with("HP")).unwrap();
+        assert!(hp.contains(" 35 "));
+        assert!(hp.ends_with("(+25)"));
+        let total = lines
+            .iter()
+            .find(|line| line.starts_with("ごうけい"))
+            .unwrap();
+        assert!(total.contains("125"));
+        assert!(total.ends_with("170 (+45)"));
+    }
+
+    #[test]
+    fn test_format_comparison_aligns_sprites_to_bottom() {
+        let mut tall = column("A", "A", &[]);
+        tall.sprite = vec!["a1".to_string(), "a2".to_string()];
+        let mut short = column("B", "B", &[]);
+        short.sprite = vec!["b1".to_string()];
+
+        let out = format_comparison(&[tall, short]);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0].trim(), "a1");
+        assert!(lines[1].contains("a2") && lines[1].contains("b1"));
+    }
+
+    #[test]
+    fn test_format_comparison_without_info() {
+        let mut missing = column("B", "Bee", &[]);
+        missing.info = None;
+        let columns = [column("A", "Ay", &[("HP      ", 50)]), missing];
+
+        let out = strip_sgr(&format_comparison(&columns));
+        let hp = out.lines().find(|line| line.starts_with("HP")).unwrap();
+        // 取れなかった列は空欄で、差分も出さない
+        assert!(!hp.contains('('));
+        assert!(out.contains("Bee"));
+    }
+}
@@ -0,0 +1,1002 @@
+//! PokéAPI を直接クロールして names.json 相当の辞書を組み立てる。
+//!
+//! `update --online` 用。CI 配布が壊れているときの非常口なので、
+//! `.github/scripts/fetch-pokemon-data.py` と同じ結果になるように移植している。
+//! ロジックを変えるときは両方を揃えること。
+
+use anyhow::{Context, Result};
+use chrono::{SubsecRound, Utc};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use crate::language::JA_KANJI_CODE;
+use crate::models::{FormCategory, NameDictionary, NameEntry};
+
+const DEFAULT_API_BASE: &str = "https://pokeapi.co/api/v2";
+
+/// フォルム取得は species の 3 倍近いリクエストになるため並列化する
+const FORM_WORKERS: usize = 8;
+
+/// 1 リクエストあたりの試行回数（初回含む）
+const MAX_ATTEMPTS: usize = 3;
+
+#[derive(Debug, Deserialize)]
+struct SpeciesList {
+    count: usize,
+    #[serde(default)]
+    results: Vec<NamedUrl>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedUrl {
+    name: String,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalizedName {
+    name: String,
+    language: LanguageRef,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageRef {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpeciesResponse {
+    id: u32,
+    name: String,
+    #[serde(default)]
+    names: Vec<LocalizedName>,
+    #[serde(default)]
+    varieties: Vec<Variety>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Variety {
+    is_default: bool,
+    pokemon: NamedUrl,
+}
+
+#[derive(Debug, Deserialize)]
+struct PokemonResponse {
+    id: u32,
+    name: String,
+    #[serde(default)]
+    types: Vec<TypeSlot>,
+    #[serde(default)]
+    forms: Vec<NamedUrl>,
+    species: NamedUrl,
+}
+
+#[derive(Debug, Deserialize)]
+struct TypeSlot {
+    slot: u32,
+    #[serde(rename = "type")]
+    type_ref: NamedUrl,
+}
+
+#[derive(Debug, Deserialize)]
+struct FormResponse {
+    #[serde(default)]
+    form_names: Vec<LocalizedName>,
+    #[serde(default)]
+    names: Vec<LocalizedName>,
+}
+
+/// 衝突解消の途中経過。フォルムだけがスラッグを持ち、書き換え対象になる
+#[derive(Debug, Clone)]
+struct CrawledEntry {
+    entry: NameEntry,
+    slug: Option<String>,
+    species_slug: Option<String>,
+}
+
+/// 非デフォルト個体の参照（種の日本語名・ほかの言語の名前と pokemon の URL）
+struct VarietyRef {
+    base_id: u32,
+    base_ja: String,
+    base_names: BTreeMap<String, String>,
+    pokemon_url: String,
+}
+
+/// PokéAPI をクロールして辞書を生成するクローラー
+pub struct PokeApiCrawler {
+    client: Client,
+    base_url: String,
+    /// species 取得ごとの待機（API レート制限対策）
+    request_interval: Duration,
+    /// リトライ前の待機
+    retry_delay: Duration,
+}
+
+impl PokeApiCrawler {
+    /// 既定の PokéAPI を対象にクローラーを作成
+    pub fn new() -> Result<Self> {
+        Self::with_base_url(DEFAULT_API_BASE.to_string())
+    }
+
+    /// API のベースURLを指定してクローラーを作成（ミラーやテスト用）
+    pub fn with_base_url(base_url: String) -> Result<Self> {
+        // 既定の UA は PokéAPI に 403 で弾かれることがあるので明示する
+        let client = Client::builder()
+            .user_agent(concat!("poke-lookup/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            request_interval: Duration::from_millis(100),
+            retry_delay: Duration::from_secs(2),
+        })
+    }
+
+    /// 全 species とフォルムを取得して辞書を組み立てる。
+    /// 1 件でも取得に失敗したら、欠けた辞書を書き込まないようエラーにする
+    pub fn crawl(&self) -> Result<NameDictionary> {
+        eprintln!("Fetching Pokemon species data from {}...", self.base_url);
+
+        let initial: SpeciesList =
+            self.fetch_json(&format!("{}/pokemon-species?limit=1", self.base_url))?;
+        eprintln!("Total species count: {}", initial.count);
+
+        let species_list: SpeciesList = self.fetch_json(&format!(
+            "{}/pokemon-species?limit={}",
+            self.base_url, initial.count
+        ))?;
+
+        let total = species_list.results.len();
+        eprintln!("Processing {} species...", total);
+
+        let mut entries = Vec::new();
+        let mut variety_refs = Vec::new();
+        let mut error_count = 0;
+
+        for (i, species_ref) in species_list.results.iter().enumerate() {
+            let done = i + 1;
+            if done % 10 == 0 || done == total {
+                eprintln!("Progress: {}/{}", done, total);
+            }
+
+            std::thread::sleep(self.request_interval);
+
+            match self.fetch_species_entry(&species_ref.url) {
+                Ok(Some((entry, refs))) => {
+                    entries.push(entry);
+                    variety_refs.extend(refs);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    eprintln!("Error: Failed to process {}: {:#}", species_ref.name, e);
+                    error_count += 1;
+                }
+            }
+        }
+
+        // フォルム（アローラのすがた・メガシンカなど）は species ではなく
+        // pokemon-form 側にしかないので、非デフォルト個体を辿って追加する
+        eprintln!("\nProcessing {} forms...", variety_refs.len());
+
+        let results = self.fetch_form_entries(&variety_refs);
+        let mut skipped = 0;
+        let mut form_count = 0;
+        for (variety, result) in variety_refs.iter().zip(results) {
+            match result {
+                Ok(Some(entry)) => {
+                    entries.push(entry);
+                    form_count += 1;
+                }
+                Ok(None) => skipped += 1,
+                Err(e) => {
+                    eprintln!(
+                        "Error: Failed to process form {}: {:#}",
+                        variety.pokemon_url, e
+                    );
+                    error_count += 1;
+                }
+            }
+        }
+
+        eprintln!(
+            "Forms with names: {} (skipped {} without a Japanese name)",
+            form_count, skipped
+        );
+
+        if error_count > 0 {
+            return Err(anyhow::anyhow!(
+                "{} errors occurred during crawling",
+                error_count
+            ));
+        }
+
+        dedupe_en(&mut entries);
+        disambiguate_ja(&mut entries);
+
+        let mut entries: Vec<NameEntry> = entries.into_iter().map(|e| e.entry).collect();
+
+        // 日本語名は検索キー、英名は検索結果の出力値かつスプライト取得のキーなので
+        // どちらも一意でなければならない（search.rs / sprite.rs の HashMap）
+        ensure_unique(&entries, "ja", |e| &e.ja)?;
+        ensure_unique(&entries, "en", |e| &e.en)?;
+
+        entries.sort_by(|a, b| a.ja.cmp(&b.ja));
+
+        Ok(NameDictionary {
+            schema_version: 3,
+            generated_at: Utc::now().trunc_subsecs(0),
+            count: entries.len(),
+            entries,
+        })
+    }
+
+    /// 種データから名前（ja・en とほかの言語）とタイプ、非デフォルト個体の参照を取り出す。
+    /// 日本語名か英名が無い種は None
+    fn fetch_species_entry(
+        &self,
+        species_url: &str,
+    ) -> Result<Option<(CrawledEntry, Vec<VarietyRef>)>> {
+        let species: SpeciesResponse =
+            self.fetch_json(&self.rebase(species_url, "pokemon-species")?)?;
+
+        let (Some(ja), Some(en)) = (
+            find_localized_name(&species.names, "ja-hrkt"),
+            find_localized_name(&species.names, "en"),
+        ) else {
+            return Ok(None);
+        };
+
+        // タイプは pokemon 側にしか無いのでデフォルト個体を辿る
+        let types = match species.varieties.iter().find(|v| v.is_default) {
+            Some(variety) => {
+                let pokemon: PokemonResponse =
+                    self.fetch_json(&self.rebase(&variety.pokemon.url, "pokemon")?)?;
+                extract_types(&pokemon)
+            }
+            None => vec![],
+        };
+
+        let names = other_names(&species.names);
+        let refs = species
+            .varieties
+            .iter()
+            .filter(|v| !v.is_default)
+            .map(|v| VarietyRef {
+                base_id: species.id,
+                base_ja: ja.to_string(),
+                base_names: names.clone(),
+                pokemon_url: v.pokemon.url.clone(),
+            })
+            .collect();
+
+        let entry = CrawledEntry {
+            entry: NameEntry {
+                ja: ja.to_string(),
+                en: en.to_string(),
+                id: Some(species.id),
+                // 種は pokemon-species のスラッグ（デフォルト個体の deoxys-normal ではなく deoxys）
+                slug: Some(species.name.clone()),
+                types,
+                names,
+                species_id: Some(species.id),
+                is_default: Some(true),
+                ..Default::default()
+            },
+            slug: None,
+            species_slug: None,
+        };
+
+        Ok(Some((entry, refs)))
+    }
+
+    /// フォルムを FORM_WORKERS 本のスレッドで並列に取得する。結果は入力順
+    fn fetch_form_entries(&self, refs: &[VarietyRef]) -> Vec<Result<Option<CrawledEntry>>> {
+        let next = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<Result<Option<CrawledEntry>>>>> =
+            Mutex::new((0..refs.len()).map(|_| None).collect());
+
+        std::thread::scope(|scope| {
+            for _ in 0..FORM_WORKERS.min(refs.len()) {
+                scope.spawn(|| {
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(variety) = refs.get(i) else {
+                            break;
+                        };
+                        let result = self.fetch_form_entry(variety);
+                        if let Ok(mut slots) = results.lock() {
+                            slots[i] = Some(result);
+                        }
+                    }
+                });
+            }
+        });
+
+        results
+            .into_inner()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|r| r.unwrap_or_else(|| Err(anyhow::anyhow!("Form worker did not finish"))))
+            .collect()
+    }
+
+    /// 個体URLからフォルムのエントリを作る。
+    /// pokemon と pokemon-form は id 体系が別なので、pokemon 経由で form を辿る。
+    /// 日本語名を持たないフォルム（トーテム個体など）は None
+    fn fetch_form_entry(&self, variety: &VarietyRef) -> Result<Option<CrawledEntry>> {
+        let pokemon: PokemonResponse =
+            self.fetch_json(&self.rebase(&variety.pokemon_url, "pokemon")?)?;
+        let form_ref = pokemon
+            .forms
+            .first()
+            .with_context(|| format!("Pokemon {} has no forms", pokemon.name))?;
+        let form: FormResponse = self.fetch_json(&self.rebase(&form_ref.url, "pokemon-form")?)?;
+
+        // イワンコ（マイペース）のように form_names ではなく names 側にだけ
+        // 日本語名を持つフォルムがある
+        let Some(form_ja) = find_localized_name(&form.form_names, "ja-hrkt")
+            .or_else(|| find_localized_name(&form.names, "ja-hrkt"))
+        else {
+            return Ok(None);
+        };
+
+        let en = find_localized_name(&form.names, "en")
+            .map(str::to_string)
+            .unwrap_or_else(|| slug_to_en(&pokemon.name));
+
+        // ほかの言語も en と同じく names の完成した名前を優先し、
+        // 無ければ種名と form_names の呼称から組み立てる
+        let names = variety
+            .base_names
+            .iter()
+            .filter_map(|(lang, base)| {
+                let name = match find_localized_name(&form.names, lang) {
+                    Some(full) => full.to_string(),
+                    None => {
+                        compose_localized(lang, base, find_localized_name(&form.form_names, lang)?)
+                    }
+                };
+                Some((lang.clone(), name))
+            })
+            .collect();
+
+        let mut crawled = CrawledEntry {
+            entry: NameEntry {
+                ja: compose_ja(&variety.base_ja, form_ja),
+                en,
+                id: Some(pokemon.id),
+                slug: Some(pokemon.name.clone()),
+                types: extract_types(&pokemon),
+                names,
+                species_id: Some(variety.base_id),
+                is_default: Some(false),
+                ..Default::default()
+            },
+            slug: Some(pokemon.name.clone()),
+            species_slug: Some(pokemon.species.name.clone()),
+        };
+        // 種名を除いたスラッグ（alola / mega-x など）。分類もここから決める
+        let form_slug = form_tokens(&crawled).join("-");
+        crawled.entry.form_category = Some(FormCategory::from_form_slug(&form_slug));
+        crawled.entry.form_slug = Some(form_slug);
+        Ok(Some(crawled))
+    }
+
+    /// 応答中の絶対URLから id だけを取り出し、base_url に根ざして組み立て直す。
+    /// リモートURLを追従しないので、ミラーやテスト用サーバーにも閉じて動く
+    fn rebase(&self, url: &str, resource: &str) -> Result<String> {
+        let id = url
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|id| !id.is_empty())
+            .with_context(|| format!("Malformed resource URL: {}", url))?;
+        Ok(format!("{}/{}/{}", self.base_url, resource, id))
+    }
+
+    /// JSON を取得（リトライ付き）
+    fn fetch_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let mut last_error = None;
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            match self.try_fetch_json(url) {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    if attempt < MAX_ATTEMPTS {
+                        eprintln!("  Retry {}/{}: {:#}", attempt, MAX_ATTEMPTS - 1, e);
+                        std::thread::sleep(self.retry_delay);
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No attempt was made: {}", url)))
+    }
+
+    fn try_fetch_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .with_context(|| format!("Failed to fetch {}", url))?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to fetch {}: HTTP {}",
+                url,
+                response.status()
+            ));
+        }
+
+        response
+            .json::<T>()
+            .with_context(|| format!("Failed to parse JSON from {}", url))
+    }
+
+    #[cfg(test)]
+    pub fn for_test(base_url: String) -> Self {
+        Self {
+            client: Client::new(),
+            base_url,
+            request_interval: Duration::ZERO,
+            retry_delay: Duration::ZERO,
+        }
+    }
+}
+
+/// 名前リストから指定言語の名前を取り出す。
+/// PokéAPI は言語コードの大文字小文字を変えることがある（ja-Hrkt → ja-hrkt）。
+/// 空や空白だけの名前は無いものとして飛ばす
+fn find_localized_name<'a>(names: &'a [LocalizedName], lang: &str) -> Option<&'a str> {
+    names
+        .iter()
+        .filter(|n| !n.name.trim().is_empty())
+        .find(|n| n.language.name.to_lowercase() == lang)
+        .map(|n| n.name.as_str())
+}
+
+/// ja-Hrkt と en 以外の名前を、小文字の言語コード → 名前にまとめる（NameEntry.names）
+fn other_names(names: &[LocalizedName]) -> BTreeMap<String, String> {
+    names
+        .iter()
+        .filter(|n| !n.name.trim().is_empty())
+        .map(|n| (n.language.name.to_lowercase(), n.name.clone()))
+        .filter(|(lang, _)| lang != "ja-hrkt" && lang != "en")
+        .collect()
+}
+
+/// pokemon データからタイプの英語スラッグを slot 昇順で取り出す
+fn extract_types(pokemon: &PokemonResponse) -> Vec<String> {
+    let mut slots: Vec<&TypeSlot> = pokemon.types.iter().collect();
+    slots.sort_by_key(|t| t.slot);
+    slots.iter().map(|t| t.type_ref.name.clone()).collect()
+}
+
+/// 種名とフォルム名から表示用の日本語名を組み立てる。
+///
+/// PokéAPI の form_names には、メガフシギバナのように単体で完結するものと、
+/// アローラのすがたのようにフォルムの呼称だけのものがある。後者は種名を合成する。
+fn compose_ja(base_ja: &str, form_ja: &str) -> String {
+    if form_ja.contains(base_ja) {
+        form_ja.to_string()
+    } else {
+        format!("{}（{}）", base_ja, form_ja)
+    }
+}
+
+/// compose_ja のほかの言語版。漢字圏は全角括弧、それ以外は半角括弧で合成する
+fn compose_localized(lang: &str, base: &str, form: &str) -> String {
+    if matches!(lang, "ja" | "zh-hans" | "zh-hant") {
+        compose_ja(base, form)
+    } else if form.contains(base) {
+        form.to_string()
+    } else {
+        format!("{} ({})", base, form)
+    }
+}
+
+/// 英名を持たないフォルム（コライドン/ミライドンの各ビルド）用のフォールバック
+fn slug_to_en(slug: &str) -> String {
+    slug.split('-')
+        .map(capitalize)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Python の str.capitalize と同じく、先頭だけ大文字・残りを小文字にする
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/// 英名が衝突するフォルムをスラッグ由来の名前に置き換える。
+///
+/// 英名は検索結果の出力値であり、スプライト取得のキーでもあるため一意である必要がある。
+/// メガイエッサン♂/♀ のように PokéAPI 側で同じ英名を持つフォルムが存在する。
+/// 種のエントリは書き換えず、フォルム側だけを変える。
+fn dedupe_en(entries: &mut [CrawledEntry]) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for e in entries.iter() {
+        *counts.entry(e.entry.en.clone()).or_default() += 1;
+    }
+
+    for e in entries.iter_mut() {
+        if counts.get(&e.entry.en).is_some_and(|&c| c > 1)
+            && let Some(ref slug) = e.slug
+        {
+            e.entry.en = slug_to_en(slug);
+        }
+    }
+}
+
+/// 個体スラッグから種名を除いた、フォルムを表すトークン列
+fn form_tokens(entry: &CrawledEntry) -> Vec<String> {
+    let (Some(slug), Some(species_slug)) = (&entry.slug, &entry.species_slug) else {
+        return vec![];
+    };
+    let species_tokens: HashSet<&str> = species_slug.split('-').collect();
+    slug.split('-')
+        .filter(|t| !species_tokens.contains(t))
+        .map(str::to_string)
+        .collect()
+}
+
+/// 日本語名が衝突するフォルムに英語の識別子を付けて一意にする。
+///
+/// メテノのりゅうせいのすがた（色違い 7 件）のように、英語では区別される
+/// フォルムが日本語では同名になることがある。検索キーは日本語名なので
+/// 同名のままでは選び分けられない。識別子はグループ内で差分になるトークン
+/// だけを使う（Orange / Combat など）。種のエントリは書き換えない。
+/// 漢字まじりの表記（names の ja）にも同じ識別子を付ける。
+fn disambiguate_ja(entries: &mut [CrawledEntry]) {
+    // 出現順を保ったまま日本語名でまとめる
+    let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (i, e) in entries.iter().enumerate() {
+        groups.entry(e.entry.ja.clone()).or_default().push(i);
+    }
+
+    for indices in groups.values() {
+        if indices.len() < 2 {
+            continue;
+        }
+
+        let forms: Vec<usize> = indices
+            .iter()
+            .copied()
+            .filter(|&i| entries[i].slug.is_some())
+            .collect();
+        if forms.is_empty() {
+            continue;
+        }
+
+        let token_lists: Vec<Vec<String>> =
+            forms.iter().map(|&i| form_tokens(&entries[i])).collect();
+        let shared: HashSet<&String> = token_lists
+            .iter()
+            .skip(1)
+            .fold(token_lists[0].iter().collect(), |acc, tokens| {
+                acc.into_iter().filter(|t| tokens.contains(t)).collect()
+            });
+
+        for (&i, tokens) in forms.iter().zip(&token_lists) {
+            let distinct: Vec<&String> = tokens.iter().filter(|t| !shared.contains(t)).collect();
+            let distinct = if distinct.is_empty() {
+                tokens.iter().collect()
+            } else {
+                distinct
+            };
+            let label = distinct
+                .iter()
+                .map(|t| capitalize(t))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let entry = &mut entries[i].entry;
+            entry.ja = append_label(&entry.ja, &label);
+            if let Some(kanji) = entry.names.get_mut(JA_KANJI_CODE) {
+                *kanji = append_label(kanji, &label);
+            }
+        }
+    }
+}
+
+/// 日本語名に識別子を足す。
+/// 「ロコン（アローラのすがた）」のように既に括弧付きなら中に足す
+fn append_label(ja: &str, label: &str) -> String {
+    match ja.strip_suffix('）') {
+        Some(head) => format!("{}・{}）", head, label),
+        None => format!("{}（{}）", ja, label),
+    }
+}
+
+fn ensure_unique(
+    entries: &[NameEntry],
+    field: &str,
+    key: impl Fn(&NameEntry) -> &str,
+) -> Result<()> {
+    let mut seen = HashSet::new();
+    let mut duplicates: Vec<&str> = entries
+        .iter()
+        .map(&key)
+        .filter(|value| !seen.insert(*value))
+        .collect();
+    if duplicates.is_empty() {
+        return Ok(());
+    }
+    duplicates.sort_unstable();
+    duplicates.dedup();
+    Err(anyhow::anyhow!(
+        "Duplicate {} names: {:?}",
+        field,
+        duplicates
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    fn form(ja: &str, en: &str, slug: &str, species_slug: &str) -> CrawledEntry {
+        CrawledEntry {
+            entry: NameEntry {
+                ja: ja.to_string(),
+                en: en.to_string(),
+                id: None,
+                types: vec![],
+                ..Default::default()
+            },
+            slug: Some(slug.to_string()),
+            species_slug: Some(species_slug.to_string()),
+        }
+    }
+
+    fn species(ja: &str, en: &str) -> CrawledEntry {
+        CrawledEntry {
+            entry: NameEntry {
+                ja: ja.to_string(),
+                en: en.to_string(),
+                id: None,
+                types: vec![],
+                ..Default::default()
+            },
+            slug: None,
+            species_slug: None,
+        }
+    }
+
+    #[test]
+    fn test_localized_names_skip_blank() {
+        let name = |name: &str, lang: &str| LocalizedName {
+            name: name.to_string(),
+            language: LanguageRef {
+                name: lang.to_string(),
+            },
+        };
+        let names = vec![
+            name("", "ja-Hrkt"),
+            name("ピカチュウ", "ja-Hrkt"),
+            name("  ", "en"),
+            name(" ", "fr"),
+            name("Pikachu", "de"),
+        ];
+        assert_eq!(find_localized_name(&names, "ja-hrkt"), Some("ピカチュウ"));
+        assert_eq!(find_localized_name(&names, "en"), None);
+        assert_eq!(
+            other_names(&names),
+            BTreeMap::from([("de".to_string(), "Pikachu".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_compose_ja() {
+        // 単体で完結するフォルム名はそのまま、呼称だけなら種名と合成する
+        assert_eq!(compose_ja("フシギバナ", "メガフシギバナ"), "メガフシギバナ");
+        assert_eq!(
+            compose_ja("ロコン", "アローラのすがた"),
+            "ロコン（アローラのすがた）"
+        );
+    }
+
+    #[test]
+    fn test_compose_localized() {
+        assert_eq!(
+            compose_localized("fr", "Goupix", "Forme d’Alola"),
+            "Goupix (Forme d’Alola)"
+        );
+        assert_eq!(
+            compose_localized("fr", "Florizarre", "Méga-Florizarre"),
+            "Méga-Florizarre"
+        );
+        assert_eq!(
+            compose_localized("zh-hans", "六尾", "阿罗拉的样子"),
+            "六尾（阿罗拉的样子）"
+        );
+    }
+
+    #[test]
+    fn test_slug_to_en() {
+        assert_eq!(
+            slug_to_en("koraidon-limited-build"),
+            "Koraidon Limited Build"
+        );
+        assert_eq!(slug_to_en("MEOWSTIC-mega"), "Meowstic Mega");
+    }
+
+    #[test]
+    fn test_dedupe_en_rewrites_only_forms() {
+        let mut entries = vec![
+            species("ニャオニクス", "Meowstic"),
+            form(
+                "メガニャオニクス♂",
+                "Mega Meowstic",
+                "meowstic-male-mega",
+                "meowstic",
+            ),
+            form(
+                "メガニャオニクス♀",
+                "Mega Meowstic",
+                "meowstic-female-mega",
+                "meowstic",
+            ),
+        ];
+        dedupe_en(&mut entries);
+
+        assert_eq!(entries[0].entry.en, "Meowstic");
+        assert_eq!(entries[1].entry.en, "Meowstic Male Mega");
+        assert_eq!(entries[2].entry.en, "Meowstic Female Mega");
+    }
+
+    #[test]
+    fn test_disambiguate_ja_uses_distinct_tokens() {
+        let mut entries = vec![
+            form(
+                "メテノ（りゅうせいのすがた）",
+                "Minior Orange Meteor",
+                "minior-orange-meteor",
+                "minior",
+            ),
+            form(
+                "メテノ（りゅうせいのすがた）",
+                "Minior Red Meteor",
+                "minior-red-meteor",
+                "minior",
+            ),
+            form(
+                "ケンタロス（パルデアのすがた）",
+                "Tauros",
+                "tauros-paldea-combat-breed",
+                "tauros",
+            ),
+        ];
+        entries[0]
+            .entry
+            .names
+            .insert("ja".to_string(), "メテノ（流星の姿）".to_string());
+        disambiguate_ja(&mut entries);
+
+        // 共通の meteor は落とし、括弧の中に差分だけを足す。漢字の表記にも同じく
+        assert_eq!(entries[0].entry.ja, "メテノ（りゅうせいのすがた・Orange）");
+        assert_eq!(entries[0].entry.names["ja"], "メテノ（流星の姿・Orange）");
+        assert_eq!(entries[1].entry.ja, "メテノ（りゅうせいのすがた・Red）");
+        // 衝突していないものは触らない
+        assert_eq!(entries[2].entry.ja, "ケンタロス（パルデアのすがた）");
+    }
+
+    #[test]
+    fn test_disambiguate_ja_without_parentheses() {
+        let mut entries = vec![
+            species("ピカチュウ", "Pikachu"),
+            form(
+                "ピカチュウ",
+                "Pikachu Cap",
+                "pikachu-original-cap",
+                "pikachu",
+            ),
+        ];
+        disambiguate_ja(&mut entries);
+
+        assert_eq!(entries[0].entry.ja, "ピカチュウ");
+        assert_eq!(entries[1].entry.ja, "ピカチュウ（Original Cap）");
+    }
+
+    #[test]
+    fn test_ensure_unique() {
+        let entries = vec![species("A", "X").entry, species("A", "Y").entry];
+        assert!(ensure_unique(&entries, "en", |e| &e.en).is_ok());
+        let err = ensure_unique(&entries, "ja", |e| &e.ja).unwrap_err();
+        assert!(err.to_string().contains("Duplicate ja names"));
+    }
+
+    /// 種 2 件（片方にフォルム 1 件）の PokéAPI をモックする。
+    /// 応答中の URL は本物の pokeapi.co を指すが、クローラーは id だけを使う
+    fn mock_pokeapi(server: &MockServer) {
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/pokemon-species")
+                .query_param("limit", "1");
+            then.status(200).json_body(serde_json::json!({
+                "count": 2,
+                "results": [{"name": "bulbasaur", "url": "https://pokeapi.co/api/v2/pokemon-species/1/"}]
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/pokemon-species")
+                .query_param("limit", "2");
+            then.status(200).json_body(serde_json::json!({
+                "count": 2,
+                "results": [
+                    {"name": "bulbasaur", "url": "https://pokeapi.co/api/v2/pokemon-species/1/"},
+                    {"name": "vulpix", "url": "https://pokeapi.co/api/v2/pokemon-species/37/"}
+                ]
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon-species/1");
+            then.status(200).json_body(serde_json::json!({
+                "id": 1,
+                "name": "bulbasaur",
+                "names": [
+                    {"name": "フシギダネ", "language": {"name": "ja-Hrkt"}},
+                    {"name": "Bulbasaur", "language": {"name": "en"}},
+                    {"name": "Bulbizarre", "language": {"name": "fr"}}
+                ],
+                "varieties": [
+                    {"is_default": true, "pokemon": {"name": "bulbasaur", "url": "https://pokeapi.co/api/v2/pokemon/1/"}}
+                ]
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon/1");
+            then.status(200).json_body(serde_json::json!({
+                "id": 1,
+                "name": "bulbasaur",
+                "types": [
+                    {"slot": 2, "type": {"name": "poison", "url": "x"}},
+                    {"slot": 1, "type": {"name": "grass", "url": "x"}}
+                ],
+                "forms": [{"name": "bulbasaur", "url": "https://pokeapi.co/api/v2/pokemon-form/1/"}],
+                "species": {"name": "bulbasaur", "url": "https://pokeapi.co/api/v2/pokemon-species/1/"}
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon-species/37");
+            then.status(200).json_body(serde_json::json!({
+                "id": 37,
+                "name": "vulpix",
+                "names": [
+                    {"name": "ロコン", "language": {"name": "ja-hrkt"}},
+                    {"name": "Vulpix", "language": {"name": "en"}},
+                    {"name": "Goupix", "language": {"name": "fr"}},
+                    {"name": "ロコン", "language": {"name": "ja"}},
+                    {"name": "六尾", "language": {"name": "zh-Hans"}}
+                ],
+                "varieties": [
+                    {"is_default": true, "pokemon": {"name": "vulpix", "url": "https://pokeapi.co/api/v2/pokemon/37/"}},
+                    {"is_default": false, "pokemon": {"name": "vulpix-alola", "url": "https://pokeapi.co/api/v2/pokemon/10103/"}}
+                ]
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon/37");
+            then.status(200).json_body(serde_json::json!({
+                "id": 37,
+                "name": "vulpix",
+                "types": [{"slot": 1, "type": {"name": "fire", "url": "x"}}],
+                "forms": [{"name": "vulpix", "url": "https://pokeapi.co/api/v2/pokemon-form/37/"}],
+                "species": {"name": "vulpix", "url": "https://pokeapi.co/api/v2/pokemon-species/37/"}
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon/10103");
+            then.status(200).json_body(serde_json::json!({
+                "id": 10103,
+                "name": "vulpix-alola",
+                "types": [{"slot": 1, "type": {"name": "ice", "url": "x"}}],
+                "forms": [{"name": "vulpix-alola", "url": "https://pokeapi.co/api/v2/pokemon-form/10142/"}],
+                "species": {"name": "vulpix", "url": "https://pokeapi.co/api/v2/pokemon-species/37/"}
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon-form/10142");
+            then.status(200).json_body(serde_json::json!({
+                "form_names": [
+                    {"name": "アローラのすがた", "language": {"name": "ja-Hrkt"}},
+                    {"name": "Forme d’Alola", "language": {"name": "fr"}},
+                    {"name": "アローラの姿", "language": {"name": "ja"}}
+                ],
+                "names": []
+            }));
+        });
+    }
+
+    #[test]
+    fn test_crawl_builds_schema_v3_dictionary() {
+        let server = MockServer::start();
+        mock_pokeapi(&server);
+
+        let crawler = PokeApiCrawler::for_test(server.url(""));
+        let dict = crawler.crawl().expect("crawl should succeed");
+
+        assert_eq!(dict.schema_version, 3);
+        assert_eq!(dict.count, 3);
+        assert!(dict.validate().is_ok());
+
+        // 日本語名で並べ替え済み
+        let names: Vec<&str> = dict.entries.iter().map(|e| e.ja.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["フシギダネ", "ロコン", "ロコン（アローラのすがた）"]
+        );
+
+        // タイプは slot 昇順
+        assert_eq!(dict.entries[0].types, vec!["grass", "poison"]);
+        assert_eq!(dict.entries[0].id, Some(1));
+
+        // 英名の無いフォルムはスラッグ由来、id は pokemon 側
+        let alola = &dict.entries[2];
+        assert_eq!(alola.en, "Vulpix Alola");
+        assert_eq!(alola.id, Some(10103));
+        assert_eq!(alola.types, vec!["ice"]);
+
+        // フォルムは元の種の id・種名を除いたスラッグ・分類を持つ
+        assert_eq!(alola.species_id, Some(37));
+        assert_eq!(alola.slug.as_deref(), Some("vulpix-alola"));
+        assert_eq!(dict.entries[1].slug.as_deref(), Some("vulpix"));
+        assert_eq!(alola.form_slug.as_deref(), Some("alola"));
+        assert_eq!(alola.is_default, Some(false));
+        assert_eq!(alola.form_category, Some(FormCategory::Regional));
+        assert_eq!(dict.entries[1].is_default, Some(true));
+        assert_eq!(dict.entries[1].form_category, None);
+
+        // ほかの言語は小文字の言語コードで持ち、フォルムは種名と呼称から組み立てる。
+        // 呼称の無い言語（zh-hans）は持たない
+        assert_eq!(
+            dict.entries[1].names.get("zh-hans").map(String::as_str),
+            Some("六尾")
+        );
+        assert_eq!(
+            alola.names.get("fr").map(String::as_str),
+            Some("Goupix (Forme d’Alola)")
+        );
+        assert!(!alola.names.contains_key("zh-hans"));
+        // 漢字まじりの日本語名もカタカナと同じく全角括弧で組み立てる
+        assert_eq!(alola.ja_kanji(), Some("ロコン（アローラの姿）"));
+        assert!(!dict.entries[0].names.contains_key("en"));
+    }
+
+    #[test]
+    fn test_crawl_fails_when_any_request_fails() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon-species");
+            then.status(200).json_body(serde_json::json!({
+                "count": 1,
+                "results": [{"name": "bulbasaur", "url": "https://pokeapi.co/api/v2/pokemon-species/1/"}]
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon-species/1");
+            then.status(500);
+        });
+
+        let crawler = PokeApiCrawler::for_test(server.url(""));
+        let err = crawler.crawl().unwrap_err();
+        assert!(err.to_string().contains("1 errors occurred"));
+    }
+}
@@ -19,6 +19,9 @@ use std::thread::JoinHandle;
 #[cfg(feature = "cries")]
 const CRY_FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
 
+#[cfg(feature = "cries")]
+const CRY_BASE_URL: &str = "https://raw.githubusercontent.com/PokeAPI/cries/main";
+
 #[cfg(feature = "cries")]
 pub struct CryService {
     cache_dir: PathBuf,
@@ -61,23 +64,9 @@ impl SinkSlot {
 
 #[cfg(feature = "cries")]
 impl CryService {
-    pub fn new() -> Result<Self> {
-        use crate::data::DataLoader;
-
-        let project_dirs = ProjectDirs::from("", "", "poke-lookup")
-            .or_else(|| ProjectDirs::from("dev", "poke-lookup", "poke-lookup"))
-            .context("Failed to determine project directories")?;
-
-        let cache_dir = project_dirs.data_dir().join("cries");
-
-        if !cache_dir.exists() {
-            std::fs::create_dir_all(&cache_dir).with_context(|| {
-                format!(
-                    "Failed to create cry cache directory: {}",
-                    cache_dir.display()
-                )
-            })?;
-        }
+    /// id は `loader` の辞書（--dict）から引く
+    pub fn with_loader(loader: &crate::data::DataLoader) -> Result<Self> {
+        let cache_dir = default_cache_dir()?;
 
         // 取得スレッドは終了前に join されるので、応答が返らないと CLI 自体が
         // 止まる。鳴き声は付加機能なので、待たせるくらいなら諦める
@@ -87,7 +76,6 @@ impl CryService {
             .build()
             .context("Failed to create HTTP client")?;
 
-        let loader = DataLoader::new()?;
         let dictionary = loader.load_dictionary()?;
         let id_map = dictionary
             .entries
@@ -98,7 +86,7 @@ impl CryService {
         Ok(Self {
             cache_dir,
             client,
-            base_url: "https://raw.githubusercontent.com/PokeAPI/cries/main".to_string(),
+            base_url: CRY_BASE_URL.to_string(),
             id_map,
             sink: Arc::new(Mutex::new(SinkSlot::Opening(std::thread::spawn(
                 Self::open_sink,
@@ -166,7 +154,7 @@ impl CryService {
     }
 
     fn cry_url(&self, pokemon_id: u32) -> String {
-        format!("{}/cries/pokemon/latest/{}.ogg", self.base_url, pokemon_id)
+        cry_url(&self.base_url, pokemon_id)
     }
 
     // テストでは音声デバイスを開かない（CIランナーには存在しないため）
@@ -184,10 +172,98 @@ impl CryService {
     }
 }
 
+/// 鳴き声をキャッシュに落とすだけの取得係（prefetch 用）。
+/// CryService と違い音声デバイスを開かず、スレッド間で共有できる
+#[cfg(feature = "cries")]
+pub struct CryFetcher {
+    cache_dir: PathBuf,
+    client: Client,
+    base_url: String,
+}
+
+#[cfg(feature = "cries")]
+impl CryFetcher {
+    pub fn new() -> Result<Self> {
+        let client = Client::builder()
+            .user_agent(concat!("poke-lookup/", env!("CARGO_PKG_VERSION")))
+            .timeout(CRY_FETCH_TIMEOUT)
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            cache_dir: default_cache_dir()?,
+            client,
+            base_url: CRY_BASE_URL.to_string(),
+        })
+    }
+
+    /// キャッシュに無ければ取得する。取得したら true、既にあれば false
+    pub fn fetch(&self, pokemon_id: u32) -> Result<bool> {
+        let cry_path = self.cache_dir.join(format!("{}.ogg", pokemon_id));
+        download_if_missing(
+            &self.client,
+            &cry_url(&self.base_url, pokemon_id),
+            &cry_path,
+        )
+    }
+
+    #[cfg(test)]
+    pub fn with_base_url(cache_dir: PathBuf, base_url: String) -> Self {
+        Self {
+            cache_dir,
+            client: Client::new(),
+            base_url,
+        }
+    }
+}
+
+/// 音声として最後まで読めるか（cache verify 用）。
+/// 中身で形式を判定するので、拡張子 .ogg の MP3 もそのまま渡せる
+#[cfg(feature = "cries")]
+pub fn is_decodable(path: &Path) -> bool {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    match rodio::Decoder::new(BufReader::new(file)) {
+        Ok(decoder) => decoder.count() > 0,
+        Err(_) => false,
+    }
+}
+
+/// <data_dir>/cries。無ければ作る
 #[cfg(feature = "cries")]
-fn download_if_missing(client: &Client, url: &str, cry_path: &Path) -> Result<()> {
+fn default_cache_dir() -> Result<PathBuf> {
+    let project_dirs = ProjectDirs::from("", "", "poke-lookup")
+        .or_else(|| ProjectDirs::from("dev", "poke-lookup", "poke-lookup"))
+        .context("Failed to determine project directories")?;
+
+    let cache_dir = project_dirs.data_dir().join("cries");
+
+    if !cache_dir.exists() {
+        std::fs::create_dir_all(&cache_dir).with_context(|| {
+            format!(
+                "Failed to create cry cache directory: {}",
+                cache_dir.display()
+            )
+        })?;
+    }
+
+    Ok(cache_dir)
+}
+
+#[cfg(feature = "cries")]
+fn cry_url(base_url: &str, pokemon_id: u32) -> String {
+    format!("{}/cries/pokemon/latest/{}.ogg", base_url, pokemon_id)
+}
+
+/// 取得したら true、既にキャッシュにあれば false
+#[cfg(feature = "cries")]
+fn download_if_missing(client: &Client, url: &str, cry_path: &Path) -> Result<bool> {
     if cry_path.exists() {
-        return Ok(());
+        return Ok(false);
     }
 
     let response = client
@@ -219,7 +295,7 @@ fn download_if_missing(client: &Client, url: &str, cry_path: &Path) -> Result<()
         )
     })?;
 
-    Ok(())
+    Ok(true)
 }
 
 /// 再生中のプレイヤーを `player_slot` に置くので、呼び出し側から stop() できる
@@ -347,7 +423,7 @@ mod tests {
             &cry_path,
         );
 
-        assert!(result.is_ok());
+        assert!(!result.unwrap());
         assert_eq!(fs::read(&cry_path).unwrap(), b"cached_audio");
     }
 
@@ -374,11 +450,64 @@ mod tests {
             &cry_path,
         );
 
-        assert!(result.is_ok());
+        assert!(result.unwrap());
         assert_eq!(fs::read(&cry_path).unwrap(), mock_audio);
         mock.assert();
     }
 
+    /// 無音 100 サンプルの 16bit モノラル WAV
+    fn silent_wav() -> Vec<u8> {
+        let samples = 100u32;
+        let data_len = samples * 2;
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // モノラル
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(&16000u32.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        wav.resize(wav.len() + data_len as usize, 0);
+        wav
+    }
+
+    #[test]
+    fn test_is_decodable() {
+        let temp_dir = tempdir().unwrap();
+        let good = temp_dir.path().join("1.ogg");
+        let bad = temp_dir.path().join("2.ogg");
+        fs::write(&good, silent_wav()).unwrap();
+        fs::write(&bad, b"not audio").unwrap();
+
+        assert!(is_decodable(&good));
+        assert!(!is_decodable(&bad));
+        assert!(!is_decodable(&temp_dir.path().join("missing.ogg")));
+    }
+
+    #[test]
+    fn test_cry_fetcher_downloads_once() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/cries/pokemon/latest/25.ogg");
+            then.status(200).body(b"audio");
+        });
+
+        let temp_dir = tempdir().unwrap();
+        let fetcher = CryFetcher::with_base_url(temp_dir.path().to_path_buf(), server.url(""));
+
+        assert!(fetcher.fetch(25).unwrap());
+        assert!(!fetcher.fetch(25).unwrap());
+        assert_eq!(fs::read(temp_dir.path().join("25.ogg")).unwrap(), b"audio");
+        mock.assert_hits(1);
+    }
+
     #[test]
     fn test_download_leaves_no_partial_file_behind() {
         use httpmock::prelude::*;
@@ -112,12 +112,14 @@ mod tests {
                     en: "Pikachu".to_string(),
                     id: None,
                     types: vec![],
+                    ..Default::default()
                 },
                 NameEntry {
                     ja: "フシギダネ".to_string(),
                     en: "Bulbasaur".to_string(),
                     id: None,
                     types: vec![],
+                    ..Default::default()
                 },
             ],
         }
@@ -0,0 +1,371 @@
+//! 進化の系統（evolution-chain）。PokéAPI の入れ子の木を図鑑の並び
+//! （親の直後に子）の配列に平らにして持ち、木の描画と確認画面での移動
+//! （進化前・進化後・分岐）に使う。
+//!
+//! 名前は辞書から引くので、ここでは種の id と進化条件（日本語に整形済み）だけを持つ。
+
+use serde::{Deserialize, Serialize};
+
+/// /evolution-chain/{id} の応答
+#[derive(Debug, Deserialize)]
+pub struct ChainResponse {
+    pub chain: ChainLink,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChainLink {
+    species: SpeciesRef,
+    #[serde(default)]
+    evolution_details: Vec<EvolutionDetail>,
+    #[serde(default)]
+    evolves_to: Vec<ChainLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpeciesRef {
+    url: String,
+}
+
+/// 進化条件。作品ごとに複数並ぶことがあるが、先頭（最初の作品）だけを見る
+#[derive(Debug, Deserialize)]
+pub struct EvolutionDetail {
+    trigger: Slug,
+    #[serde(default)]
+    min_level: Option<u32>,
+    #[serde(default)]
+    min_happiness: Option<u32>,
+    #[serde(default)]
+    item: Option<Slug>,
+    #[serde(default)]
+    held_item: Option<Slug>,
+    #[serde(default)]
+    time_of_day: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Slug {
+    name: String,
+}
+
+/// 系統内の1匹
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvolutionNode {
+    /// 種の id（図鑑番号）
+    pub species_id: u32,
+    /// 進化前の位置。最初の1匹は None
+    pub parent: Option<usize>,
+    /// 進化条件（「Lv.16」「かみなりのいし」「通信交換」など）。最初の1匹は None
+    pub condition: Option<String>,
+}
+
+/// 系統全体。nodes[0] が最初の1匹で、親は必ず子より前に来る
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvolutionChain {
+    pub nodes: Vec<EvolutionNode>,
+}
+
+/// 確認画面での移動の向き
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// 進化前
+    Back,
+    /// 進化後（分岐していれば最初の1匹）
+    Forward,
+    /// 同じ進化前を持つ前の分岐
+    Previous,
+    /// 同じ進化前を持つ次の分岐
+    Next,
+}
+
+impl EvolutionChain {
+    /// API の木を平らにする。`item_name` は道具のスラッグを日本語名にする
+    pub fn from_link(link: &ChainLink, item_name: &mut dyn FnMut(&str) -> String) -> Self {
+        let mut chain = Self { nodes: Vec::new() };
+        chain.push(link, None, item_name);
+        chain
+    }
+
+    fn push(
+        &mut self,
+        link: &ChainLink,
+        parent: Option<usize>,
+        item_name: &mut dyn FnMut(&str) -> String,
+    ) {
+        // species の URL が読めない枝は辿りようがないので捨てる
+        let Some(species_id) = species_id_from_url(&link.species.url) else {
+            return;
+        };
+        let condition = parent.and_then(|_| {
+            link.evolution_details
+                .first()
+                .map(|detail| format_condition(detail, item_name))
+        });
+        let index = self.nodes.len();
+        self.nodes.push(EvolutionNode {
+            species_id,
+            parent,
+            condition,
+        });
+        for child in &link.evolves_to {
+            self.push(child, Some(index), item_name);
+        }
+    }
+
+    /// 進化しない（1匹だけの）系統か
+    pub fn is_single(&self) -> bool {
+        self.nodes.len() <= 1
+    }
+
+    pub fn position(&self, species_id: u32) -> Option<usize> {
+        self.nodes.iter().position(|n| n.species_id == species_id)
+    }
+
+    fn children(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(move |(_, n)| n.parent == Some(index))
+            .map(|(i, _)| i)
+    }
+
+    /// index から direction に動いた先。行き止まりなら None
+    pub fn step(&self, index: usize, direction: Direction) -> Option<usize> {
+        let node = self.nodes.get(index)?;
+        match direction {
+            Direction::Back => node.parent,
+            Direction::Forward => self.children(index).next(),
+            Direction::Previous | Direction::Next => {
+                let siblings: Vec<usize> = self.children(node.parent?).collect();
+                let at = siblings.iter().position(|&i| i == index)?;
+                match direction {
+                    Direction::Previous => at.checked_sub(1).map(|at| siblings[at]),
+                    _ => siblings.get(at + 1).copied(),
+                }
+            }
+        }
+    }
+
+    /// 木の形で1匹1行に並べる。current の行には ▶ を付ける。
+    /// `name` は種の id から表示名を引く（辞書に無ければ呼び出し側で番号などにする）
+    pub fn format_tree(&self, current: Option<usize>, name: &dyn Fn(u32) -> String) -> String {
+        let mut out = String::new();
+        if !self.nodes.is_empty() {
+            self.format_node(0, "", true, current, name, &mut out);
+        }
+        out
+    }
+
+    fn format_node(
+        &self,
+        index: usize,
+        indent: &str,
+        last: bool,
+        current: Option<usize>,
+        name: &dyn Fn(u32) -> String,
+        out: &mut String,
+    ) {
+        let node = &self.nodes[index];
+        let marker = if current == Some(index) { "▶ " } else { "  " };
+        let (branch, child_indent) = match node.parent {
+            None => (String::new(), indent.to_string()),
+            Some(_) if last => (format!("{}└ ", indent), format!("{}   ", indent)),
+            Some(_) => (format!("{}├ ", indent), format!("{}│  ", indent)),
+        };
+        let label = match node.condition {
+            Some(ref condition) => format!("{}（{}）", name(node.species_id), condition),
+            None => name(node.species_id),
+        };
+        out.push_str(&format!("{}{}{}\n", marker, branch, label));
+
+        let children: Vec<usize> = self.children(index).collect();
+        for (i, &child) in children.iter().enumerate() {
+            self.format_node(
+                child,
+                &child_indent,
+                i + 1 == children.len(),
+                current,
+                name,
+                out,
+            );
+        }
+    }
+}
+
+/// 進化条件を日本語にする。レベル・道具・通信交換以外は大まかな言い方に留める
+fn format_condition(detail: &EvolutionDetail, item_name: &mut dyn FnMut(&str) -> String) -> String {
+    let mut text = match detail.trigger.name.as_str() {
+        "level-up" => match (detail.min_level, detail.min_happiness) {
+            (Some(level), _) => format!("Lv.{}", level),
+            (None, Some(_)) => "なつき".to_string(),
+            (None, None) => "レベルアップ".to_string(),
+        },
+        "use-item" => match detail.item {
+            Some(ref item) => item_name(&item.name),
+            None => "どうぐ".to_string(),
+        },
+        "trade" => match detail.held_item {
+            Some(ref item) => format!("{}を持たせて通信交換", item_name(&item.name)),
+            None => "通信交換".to_string(),
+        },
+        _ => "とくべつな条件".to_string(),
+    };
+    if detail.trigger.name == "level-up"
+        && let Some(ref item) = detail.held_item
+    {
+        text = format!("{}を持たせて{}", item_name(&item.name), text);
+    }
+    match detail.time_of_day.as_str() {
+        "day" => text.push_str("・昼"),
+        "night" => text.push_str("・夜"),
+        _ => {}
+    }
+    text
+}
+
+/// `.../pokemon-species/133/` 形式の末尾から種の id を取り出す
+fn species_id_from_url(url: &str) -> Option<u32> {
+    url.trim_end_matches('/').rsplit('/').next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> EvolutionChain {
+        let response: ChainResponse = serde_json::from_str(json).unwrap();
+        EvolutionChain::from_link(&response.chain, &mut |slug| match slug {
+            "water-stone" => "みずのいし".to_string(),
+            "thunder-stone" => "かみなりのいし".to_string(),
+            other => other.to_string(),
+        })
+    }
+
+    fn species(id: u32) -> String {
+        format!(
+            r#"{{"url":"https://pokeapi.co/api/v2/pokemon-species/{}/"}}"#,
+            id
+        )
+    }
+
+    /// イーブイ → シャワーズ（みずのいし）/ サンダース（かみなりのいし）/ エーフィ（なつき・昼）
+    fn eevee() -> EvolutionChain {
+        parse(&format!(
+            r#"{{"chain":{{"species":{},"evolution_details":[],"evolves_to":[
+                {{"species":{},"evolution_details":[{{"trigger":{{"name":"use-item"}},"item":{{"name":"water-stone"}},"time_of_day":""}}],"evolves_to":[]}},
+                {{"species":{},"evolution_details":[{{"trigger":{{"name":"use-item"}},"item":{{"name":"thunder-stone"}},"time_of_day":""}}],"evolves_to":[]}},
+                {{"species":{},"evolution_details":[{{"trigger":{{"name":"level-up"}},"min_happiness":160,"time_of_day":"day"}}],"evolves_to":[]}}
+            ]}}}}"#,
+            species(133),
+            species(134),
+            species(135),
+            species(196)
+        ))
+    }
+
+    /// フシギダネ → フシギソウ（Lv.16）→ フシギバナ（Lv.32）
+    fn bulbasaur() -> EvolutionChain {
+        parse(&format!(
+            r#"{{"chain":{{"species":{},"evolution_details":[],"evolves_to":[
+                {{"species":{},"evolution_details":[{{"trigger":{{"name":"level-up"}},"min_level":16}}],"evolves_to":[
+                    {{"species":{},"evolution_details":[{{"trigger":{{"name":"level-up"}},"min_level":32}}],"evolves_to":[]}}
+                ]}}
+            ]}}}}"#,
+            species(1),
+            species(2),
+            species(3)
+        ))
+    }
+
+    fn name(id: u32) -> String {
+        match id {
+            1 => "フシギダネ",
+            2 => "フシギソウ",
+            3 => "フシギバナ",
+            133 => "イーブイ",
+            134 => "シャワーズ",
+            135 => "サンダース",
+            196 => "エーフィ",
+            _ => "?",
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn test_from_link_flattens_in_order() {
+        let chain = bulbasaur();
+        let ids: Vec<u32> = chain.nodes.iter().map(|n| n.species_id).collect();
+        assert_eq!(ids, [1, 2, 3]);
+        assert_eq!(chain.nodes[2].parent, Some(1));
+        assert_eq!(chain.nodes[0].condition, None);
+        assert_eq!(chain.nodes[1].condition.as_deref(), Some("Lv.16"));
+    }
+
+    #[test]
+    fn test_conditions() {
+        let chain = eevee();
+        let conditions: Vec<&str> = chain.nodes[1..]
+            .iter()
+            .map(|n| n.condition.as_deref().unwrap())
+            .collect();
+        assert_eq!(conditions, ["みずのいし", "かみなりのいし", "なつき・昼"]);
+    }
+
+    #[test]
+    fn test_trade_condition() {
+        let chain = parse(&format!(
+            r#"{{"chain":{{"species":{},"evolves_to":[
+                {{"species":{},"evolution_details":[{{"trigger":{{"name":"trade"}},"held_item":{{"name":"metal-coat"}}}}]}}
+            ]}}}}"#,
+            species(95),
+            species(208)
+        ));
+        assert_eq!(
+            chain.nodes[1].condition.as_deref(),
+            Some("metal-coatを持たせて通信交換")
+        );
+    }
+
+    #[test]
+    fn test_step() {
+        let chain = bulbasaur();
+        assert_eq!(chain.step(0, Direction::Forward), Some(1));
+        assert_eq!(chain.step(1, Direction::Forward), Some(2));
+        assert_eq!(chain.step(2, Direction::Forward), None);
+        assert_eq!(chain.step(2, Direction::Back), Some(1));
+        assert_eq!(chain.step(0, Direction::Back), None);
+
+        // 分岐は同じ進化前の中で上下に動く
+        let chain = eevee();
+        assert_eq!(chain.step(0, Direction::Forward), Some(1));
+        assert_eq!(chain.step(1, Direction::Next), Some(2));
+        assert_eq!(chain.step(3, Direction::Next), None);
+        assert_eq!(chain.step(2, Direction::Previous), Some(1));
+        assert_eq!(chain.step(0, Direction::Next), None);
+    }
+
+    #[test]
+    fn test_format_tree() {
+        assert_eq!(
+            bulbasaur().format_tree(Some(1), &name),
+            "  フシギダネ\n\
+             ▶ └ フシギソウ（Lv.16）\n\
+             \x20    └ フシギバナ（Lv.32）\n"
+        );
+        assert_eq!(
+            eevee().format_tree(None, &name),
+            "  イーブイ\n\
+             \x20 ├ シャワーズ（みずのいし）\n\
+             \x20 ├ サンダース（かみなりのいし）\n\
+             \x20 └ エーフィ（なつき・昼）\n"
+        );
+    }
+
+    #[test]
+    fn test_position_and_single() {
+        let chain = eevee();
+        assert_eq!(chain.position(135), Some(2));
+        assert_eq!(chain.position(25), None);
+        assert!(!chain.is_single());
+        assert!(EvolutionChain { nodes: Vec::new() }.is_single());
+    }
+}
@@ -1,16 +1,45 @@
 #[cfg(feature = "sprites")]
+use crate::evolution::{ChainResponse, EvolutionChain};
+#[cfg(feature = "sprites")]
+use crate::learnset::{Learnset, MoveSlot, format_learnset};
+#[cfg(feature = "sprites")]
 use anyhow::{Context, Result};
 #[cfg(feature = "sprites")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "sprites")]
+use directories::ProjectDirs;
+#[cfg(feature = "sprites")]
 use reqwest::blocking::Client;
 #[cfg(feature = "sprites")]
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "sprites")]
 use std::collections::HashMap;
+#[cfg(feature = "sprites")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "sprites")]
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// /pokemon/{id} は数KB程度。返らないなら諦めて情報表示を省く
 #[cfg(feature = "sprites")]
 const INFO_FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
 
+/// 情報キャッシュの書式。CachedInfo の形を変えたら上げる（古い版は読み捨てて取り直す）
+#[cfg(feature = "sprites")]
+const INFO_CACHE_SCHEMA_VERSION: u32 = 3;
+
+/// わざ名を同時に引く数。PokéAPI に負荷をかけすぎない程度
+#[cfg(feature = "sprites")]
+const MOVE_NAME_JOBS: usize = 8;
+
+/// 覚えるわざのキャッシュ（info/learnset/{id}.json）
+#[cfg(feature = "sprites")]
+const LEARNSET_RESOURCE: &str = "learnset";
+
+/// 情報キャッシュの有効期間。種族値や説明文はまず変わらないが、
+/// タイプの変更（第6世代のフェアリー追加など）はありうるので、たまに取り直す
+#[cfg(feature = "sprites")]
+const INFO_CACHE_TTL_DAYS: i64 = 30;
+
 /// 種族値スラッグ → 日本語ラベル。ゲージを揃えるため表示幅8桁に padding 済み
 /// （日本語4文字＝全角で8桁、HP は半角2文字＋空白6）
 #[cfg(feature = "sprites")]
@@ -26,14 +55,48 @@ fn stat_ja(slug: &str) -> Option<&'static str> {
     })
 }
 
+/// タマゴグループのスラッグ → 日本語名。15種で固定なので API を叩かない
+#[cfg(feature = "sprites")]
+fn egg_group_ja(slug: &str) -> Option<&'static str> {
+    Some(match slug {
+        "monster" => "かいじゅう",
+        "water1" => "すいちゅう1",
+        "water2" => "すいちゅう2",
+        "water3" => "すいちゅう3",
+        "bug" => "むし",
+        "flying" => "ひこう",
+        "ground" => "りくじょう",
+        "fairy" => "ようせい",
+        "plant" => "しょくぶつ",
+        "humanshape" => "ひとがた",
+        "mineral" => "こうぶつ",
+        "indeterminate" => "ふていけい",
+        "ditto" => "メタモン",
+        "dragon" => "ドラゴン",
+        "no-eggs" => "タマゴみはっけん",
+        _ => return None,
+    })
+}
+
 #[cfg(feature = "sprites")]
 #[derive(Debug, Deserialize)]
 struct PokemonResponse {
     types: Vec<TypeSlot>,
     stats: Vec<StatSlot>,
+    #[serde(default)]
+    abilities: Vec<AbilitySlot>,
+    /// デシメートル
+    #[serde(default)]
+    height: Option<u32>,
+    /// ヘクトグラム
+    #[serde(default)]
+    weight: Option<u32>,
     // フォルムでも正しい種を辿れるよう species の URL を使う
     #[serde(default)]
     species: Option<UrlRef>,
+    /// 覚えるわざ。情報と一緒に info/learnset/ に保存する
+    #[serde(default)]
+    moves: Vec<MoveSlot>,
 }
 
 #[cfg(feature = "sprites")]
@@ -42,10 +105,59 @@ struct UrlRef {
     url: String,
 }
 
+#[cfg(feature = "sprites")]
+#[derive(Debug, Deserialize)]
+struct AbilitySlot {
+    ability: NamedRef,
+    #[serde(default)]
+    is_hidden: bool,
+}
+
 #[cfg(feature = "sprites")]
 #[derive(Debug, Deserialize)]
 struct SpeciesResponse {
     flavor_text_entries: Vec<FlavorEntry>,
+    #[serde(default)]
+    genera: Vec<GenusEntry>,
+    #[serde(default)]
+    egg_groups: Vec<NamedRef>,
+    /// メスの割合（8分の何か）。-1 は性別不明
+    #[serde(default)]
+    gender_rate: Option<i8>,
+    #[serde(default)]
+    evolution_chain: Option<UrlRef>,
+}
+
+#[cfg(feature = "sprites")]
+#[derive(Debug, Deserialize)]
+struct GenusEntry {
+    genus: String,
+    language: NamedRef,
+}
+
+/// /ability/{name} などの多言語名
+#[cfg(feature = "sprites")]
+#[derive(Debug, Deserialize)]
+struct NamesResponse {
+    names: Vec<LocalizedName>,
+}
+
+#[cfg(feature = "sprites")]
+#[derive(Debug, Deserialize)]
+struct LocalizedName {
+    name: String,
+    language: NamedRef,
+}
+
+/// species から取る付加情報
+#[cfg(feature = "sprites")]
+#[derive(Debug, Default)]
+struct SpeciesDetails {
+    description: Option<String>,
+    genus: Option<String>,
+    egg_groups: Vec<String>,
+    gender_rate: Option<i8>,
+    evolution_chain_id: Option<u32>,
 }
 
 #[cfg(feature = "sprites")]
@@ -115,9 +227,137 @@ fn type_color(slug: &str) -> &'static str {
     }
 }
 
+/// ディスクに保存する取得結果。表示用の整形（日本語ラベル・色）は
+/// 読み出すたびに行うので、ここには API のスラッグをそのまま持つ。
+/// 特性名だけは別 API で引いた日本語名を持つ
+#[cfg(feature = "sprites")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedInfo {
+    schema_version: u32,
+    fetched_at: DateTime<Utc>,
+    /// タイプの英語スラッグ（スロット順）
+    types: Vec<String>,
+    stats: Vec<CachedStat>,
+    description: Option<String>,
+    #[serde(default)]
+    abilities: Vec<CachedAbility>,
+    #[serde(default)]
+    height: Option<u32>,
+    #[serde(default)]
+    weight: Option<u32>,
+    #[serde(default)]
+    genus: Option<String>,
+    /// タマゴグループのスラッグ
+    #[serde(default)]
+    egg_groups: Vec<String>,
+    #[serde(default)]
+    gender_rate: Option<i8>,
+    #[serde(default)]
+    evolution_chain_id: Option<u32>,
+}
+
+#[cfg(feature = "sprites")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedAbility {
+    /// 日本語名（引けなかったときはスラッグ）
+    name: String,
+    hidden: bool,
+}
+
+/// /ability/{name} などで引いた日本語名のキャッシュ。名前は変わらないので期限は無い
+#[cfg(feature = "sprites")]
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedName {
+    name: String,
+}
+
+/// 情報に付随する取得結果のキャッシュ（info/{resource}/{key}.json）。
+/// 進化の系統・覚えるわざに使い、期限は情報と同じ
+#[cfg(feature = "sprites")]
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResource<T> {
+    schema_version: u32,
+    fetched_at: DateTime<Utc>,
+    value: T,
+}
+
+#[cfg(feature = "sprites")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedStat {
+    name: String,
+    base_stat: u16,
+}
+
+#[cfg(feature = "sprites")]
+impl CachedInfo {
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        now - self.fetched_at < chrono::Duration::days(INFO_CACHE_TTL_DAYS)
+    }
+
+    fn to_info(&self) -> PokemonInfo {
+        let types = types_from_slugs(&self.types);
+
+        // ラベルを解決できた種族値だけ（順序はAPIの HP→こうげき→…→すばやさ）
+        let stats = self
+            .stats
+            .iter()
+            .filter_map(|stat| {
+                stat_ja(&stat.name).map(|label| StatEntry {
+                    label,
+                    value: stat.base_stat,
+                })
+            })
+            .collect();
+
+        PokemonInfo {
+            types,
+            stats,
+            description: self.description.clone(),
+            genus: self.genus.clone(),
+            height: self.height,
+            weight: self.weight,
+            abilities: self
+                .abilities
+                .iter()
+                .map(|ability| Ability {
+                    name: ability.name.clone(),
+                    hidden: ability.hidden,
+                })
+                .collect(),
+            egg_groups: self
+                .egg_groups
+                .iter()
+                .map(|slug| egg_group_ja(slug).map_or_else(|| slug.clone(), str::to_string))
+                .collect(),
+            gender_rate: self.gender_rate,
+            evolution_chain_id: self.evolution_chain_id,
+        }
+    }
+}
+
+/// タイプの英語スラッグ → 表示用。未知スラッグはそのまま出してフォールバック
+#[cfg(feature = "sprites")]
+fn types_from_slugs<S: AsRef<str>>(slugs: &[S]) -> Vec<PokemonType> {
+    slugs
+        .iter()
+        .map(|slug| {
+            let slug = slug.as_ref();
+            PokemonType {
+                slug: slug.to_string(),
+                ja: crate::pokemon_type::type_ja(slug)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| slug.to_string()),
+                color: type_color(slug),
+            }
+        })
+        .collect()
+}
+
 /// 表示用の1件のタイプ
 #[cfg(feature = "sprites")]
 pub struct PokemonType {
+    /// 英語スラッグ（相性の計算用）
+    pub slug: String,
     pub ja: String,
     /// チップ背景色の SGR プレフィックス。未知タイプは空（色なし）
     pub color: &'static str,
@@ -131,35 +371,59 @@ pub struct StatEntry {
     pub value: u16,
 }
 
+/// 表示用の1件の特性
+#[cfg(feature = "sprites")]
+pub struct Ability {
+    /// 日本語名（引けなかったときは英語スラッグ）
+    pub name: String,
+    /// かくれとくせいか
+    pub hidden: bool,
+}
+
 /// 画像の下に出すポケモン情報
 #[cfg(feature = "sprites")]
+#[derive(Default)]
 pub struct PokemonInfo {
     pub types: Vec<PokemonType>,
     pub stats: Vec<StatEntry>,
     /// 図鑑説明文（日本語）。取得できなければ None
     pub description: Option<String>,
+    /// 分類（「ねずみポケモン」など）
+    pub genus: Option<String>,
+    /// 高さ（デシメートル）
+    pub height: Option<u32>,
+    /// 重さ（ヘクトグラム）
+    pub weight: Option<u32>,
+    pub abilities: Vec<Ability>,
+    /// タマゴグループの日本語名
+    pub egg_groups: Vec<String>,
+    /// メスの割合（8分の何か）。-1 は性別不明
+    pub gender_rate: Option<i8>,
+    /// 進化の系統の id（fetch_evolution_chain に渡す）
+    pub evolution_chain_id: Option<u32>,
 }
 
-/// ポケモンの付加情報（タイプ・種族値）の取得を管理するサービス
+/// ポケモンの付加情報（タイプ・種族値）の取得を管理するサービス。
+/// 取得結果は id ごとにデータディレクトリの info/ にキャッシュする
 #[cfg(feature = "sprites")]
 pub struct PokemonInfoService {
     client: Client,
     base_url: String,
     id_map: HashMap<String, u32>,
+    /// None ならキャッシュしない（ディレクトリを用意できなかった場合）
+    cache_dir: Option<PathBuf>,
 }
 
 #[cfg(feature = "sprites")]
 impl PokemonInfoService {
-    pub fn new() -> Result<Self> {
-        use crate::data::DataLoader;
-
+    /// id は `loader` の辞書（--dict）から引く
+    pub fn with_loader(loader: &crate::data::DataLoader) -> Result<Self> {
         let client = Client::builder()
             .user_agent(concat!("poke-lookup/", env!("CARGO_PKG_VERSION")))
             .timeout(INFO_FETCH_TIMEOUT)
             .build()
             .context("Failed to create HTTP client")?;
 
-        let loader = DataLoader::new()?;
         let dictionary = loader.load_dictionary()?;
         let id_map = dictionary
             .entries
@@ -167,10 +431,17 @@ impl PokemonInfoService {
             .filter_map(|entry| entry.id.map(|id| (entry.en.clone(), id)))
             .collect();
 
+        // キャッシュは速度のためだけなので、作れなくても毎回取得して動かす
+        let cache_dir = ProjectDirs::from("", "", "poke-lookup")
+            .or_else(|| ProjectDirs::from("dev", "poke-lookup", "poke-lookup"))
+            .map(|dirs| dirs.data_dir().join("info"))
+            .filter(|dir| std::fs::create_dir_all(dir).is_ok());
+
         Ok(Self {
             client,
             base_url: "https://pokeapi.co/api/v2".to_string(),
             id_map,
+            cache_dir,
         })
     }
 
@@ -178,10 +449,64 @@ impl PokemonInfoService {
         self.id_map.get(english_name).copied()
     }
 
-    /// 英名からタイプと種族値を1リクエストで取得。取得できなければ None
+    /// 英名からタイプ・種族値・図鑑説明を取得。取得できなければ None。
+    ///
+    /// 期限内のキャッシュがあればネットワークに出ない。期限切れでも取得に
+    /// 失敗したら（機内・プロキシ越しなど）古いキャッシュで表示する
     pub fn fetch(&self, english_name: &str) -> Option<PokemonInfo> {
         let id = self.get_pokemon_id(english_name)?;
 
+        let cached = self.read_cache(id);
+        if let Some(ref cached) = cached
+            && cached.is_fresh(Utc::now())
+        {
+            return Some(cached.to_info());
+        }
+
+        match self.fetch_remote(id) {
+            Some((fresh, learnset, complete)) => {
+                // 説明文だけ取れなかった結果は保存しない。次回また取りに行く
+                if complete {
+                    self.write_cache(id, &fresh);
+                }
+                self.write_resource(LEARNSET_RESOURCE, id, &learnset);
+                Some(fresh.to_info())
+            }
+            None => cached.map(|cached| cached.to_info()),
+        }
+    }
+
+    /// 期限内のキャッシュが無ければ取得して保存する（prefetch 用）。
+    /// 取得したら true、既にあれば false。説明文まで取れなかったら Err（再試行させる）
+    pub fn prefetch(&self, id: u32) -> Result<bool> {
+        if self
+            .read_cache(id)
+            .is_some_and(|cached| cached.is_fresh(Utc::now()))
+        {
+            return Ok(false);
+        }
+        match self.fetch_remote(id) {
+            Some((fresh, learnset, true)) => {
+                let path = self.cache_path(id).context("Info cache is not available")?;
+                write_atomic(&path, &serde_json::to_vec(&fresh)?)?;
+                self.write_resource(LEARNSET_RESOURCE, id, &learnset);
+                Ok(true)
+            }
+            Some((_, _, false)) => Err(anyhow::anyhow!(
+                "Failed to fetch description for Pokemon ID {}",
+                id
+            )),
+            None => Err(anyhow::anyhow!(
+                "Failed to fetch info for Pokemon ID {}",
+                id
+            )),
+        }
+    }
+
+    /// /pokemon/{id} と /pokemon-species/{species_id} を取得する。
+    /// /pokemon/{id} の覚えるわざも一緒に返す（わざ一覧のために取り直さない）。
+    /// 戻り値の bool は説明文まで取得できたか（情報をキャッシュしてよいか）
+    fn fetch_remote(&self, id: u32) -> Option<(CachedInfo, Learnset, bool)> {
         let url = format!("{}/pokemon/{}", self.base_url, id);
         let response = match self.client.get(&url).send() {
             Ok(r) if r.status().is_success() => r,
@@ -190,54 +515,85 @@ impl PokemonInfoService {
 
         let body = response.json::<PokemonResponse>().ok()?;
 
-        // 未知スラッグはそのまま出してフォールバック
-        let types = body
-            .types
-            .iter()
-            .map(|slot| PokemonType {
-                ja: crate::pokemon_type::type_ja(&slot.type_ref.name)
-                    .map(str::to_string)
-                    .unwrap_or_else(|| slot.type_ref.name.clone()),
-                color: type_color(&slot.type_ref.name),
-            })
-            .collect();
+        // フォルムは form id から species を辿れない（/pokemon-species/{form_id} は
+        // 404）。応答の species URL から species id を取り出し、リクエスト自体は
+        // base_url に根ざして組み立てる（テスト可能・リモートURL追従を避ける）
+        let species = match body.species.as_ref().and_then(|sp| id_from_url(&sp.url)) {
+            Some(species_id) => self.fetch_species(species_id),
+            None => Ok(SpeciesDetails::default()),
+        };
 
-        // ラベルを解決できた種族値だけ（順序はAPIの HP→こうげき→…→すばやさ）
-        let stats = body
-            .stats
+        // 特性名は1件ずつ引く。引けなかったものはスラッグのまま出し、保存はしない
+        let mut abilities_complete = true;
+        let abilities = body
+            .abilities
             .iter()
-            .filter_map(|slot| {
-                stat_ja(&slot.stat.name).map(|label| StatEntry {
-                    label,
-                    value: slot.base_stat,
-                })
+            .map(|slot| {
+                let name = match self.resolve_name("ability", &slot.ability.name) {
+                    Ok(Some(name)) => name,
+                    Ok(None) => slot.ability.name.clone(),
+                    Err(_) => {
+                        abilities_complete = false;
+                        slot.ability.name.clone()
+                    }
+                };
+                CachedAbility {
+                    name,
+                    hidden: slot.is_hidden,
+                }
             })
             .collect();
+        let complete = species.is_ok() && abilities_complete;
+        let species = species.unwrap_or_default();
+        let learnset = Learnset::from_slots(&body.moves);
 
-        // フォルムは form id から species を辿れない（/pokemon-species/{form_id} は
-        // 404）。応答の species URL から species id を取り出し、リクエスト自体は
-        // base_url に根ざして組み立てる（テスト可能・リモートURL追従を避ける）
-        let description = body
-            .species
-            .as_ref()
-            .and_then(|sp| id_from_url(&sp.url))
-            .and_then(|species_id| self.fetch_description(species_id));
-
-        Some(PokemonInfo {
-            types,
-            stats,
-            description,
-        })
+        let info = CachedInfo {
+            schema_version: INFO_CACHE_SCHEMA_VERSION,
+            fetched_at: Utc::now(),
+            types: body
+                .types
+                .into_iter()
+                .map(|slot| slot.type_ref.name)
+                .collect(),
+            stats: body
+                .stats
+                .into_iter()
+                .map(|slot| CachedStat {
+                    name: slot.stat.name,
+                    base_stat: slot.base_stat,
+                })
+                .collect(),
+            description: species.description,
+            abilities,
+            height: body.height,
+            weight: body.weight,
+            genus: species.genus,
+            egg_groups: species.egg_groups,
+            gender_rate: species.gender_rate,
+            evolution_chain_id: species.evolution_chain_id,
+        };
+        Some((info, learnset, complete))
     }
 
-    /// species ID から日本語の図鑑説明文を1件取得。失敗時は None
-    fn fetch_description(&self, species_id: u32) -> Option<String> {
+    /// species ID から日本語の図鑑説明文（1件）・分類・タマゴグループ・性別比を取得。
+    /// 日本語が無い項目は None、取得自体に失敗したら Err
+    fn fetch_species(&self, species_id: u32) -> Result<SpeciesDetails> {
         let url = format!("{}/pokemon-species/{}", self.base_url, species_id);
-        let response = self.client.get(&url).send().ok()?;
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .with_context(|| format!("Failed to fetch species {}", species_id))?;
         if !response.status().is_success() {
-            return None;
+            return Err(anyhow::anyhow!(
+                "Failed to fetch species {}: HTTP {}",
+                species_id,
+                response.status()
+            ));
         }
-        let species = response.json::<SpeciesResponse>().ok()?;
+        let species = response
+            .json::<SpeciesResponse>()
+            .context("Failed to parse species response")?;
         // 漢字かな交じり(ja)を優先。無ければ かな(ja-Hrkt) にフォールバック
         let entry = species
             .flavor_text_entries
@@ -248,8 +604,227 @@ impl PokemonInfoService {
                     .flavor_text_entries
                     .iter()
                     .find(|e| e.language.name == "ja-Hrkt")
-            })?;
-        Some(clean_flavor(&entry.flavor_text))
+            });
+        let genus = ["ja", "ja-Hrkt"].iter().find_map(|lang| {
+            species
+                .genera
+                .iter()
+                .find(|g| g.language.name == *lang)
+                .map(|g| g.genus.clone())
+        });
+        Ok(SpeciesDetails {
+            description: entry.map(|e| clean_flavor(&e.flavor_text)),
+            genus,
+            egg_groups: species.egg_groups.into_iter().map(|g| g.name).collect(),
+            gender_rate: species.gender_rate,
+            evolution_chain_id: species
+                .evolution_chain
+                .as_ref()
+                .and_then(|chain| id_from_url(&chain.url)),
+        })
+    }
+
+    /// 進化の系統を取得。期限内のキャッシュがあればネットワークに出ず、
+    /// 取得に失敗したら古いキャッシュを使う。どちらも無ければ None
+    pub fn fetch_evolution_chain(&self, chain_id: u32) -> Option<EvolutionChain> {
+        self.fetch_resource("evolution-chain", chain_id, || {
+            self.fetch_evolution_remote(chain_id)
+        })
+    }
+
+    /// 覚えるわざ（全作品分）を取得。キャッシュの扱いは fetch_evolution_chain と同じ。
+    /// わざは情報の取得（fetch）のときに一緒に保存しているので、確認画面を経ていれば
+    /// ネットワークに出ない。無ければ情報ごと取り直す
+    pub fn fetch_learnset(&self, english_name: &str) -> Option<Learnset> {
+        let id = self.get_pokemon_id(english_name)?;
+        self.fetch_resource(LEARNSET_RESOURCE, id, || {
+            let (info, learnset, complete) = self.fetch_remote(id)?;
+            if complete {
+                self.write_cache(id, &info);
+            }
+            Some((learnset, true))
+        })
+    }
+
+    /// わざのスラッグ → 日本語名。引けなかったものは入らない。
+    /// 初めて見る一覧は件数が多いので、上限付きの並列で引く
+    pub fn move_names(&self, slugs: &[&str]) -> HashMap<String, String> {
+        let names = std::sync::Mutex::new(HashMap::new());
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..MOVE_NAME_JOBS.min(slugs.len()) {
+                scope.spawn(|| {
+                    loop {
+                        let i = next.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                        let Some(slug) = slugs.get(i) else {
+                            break;
+                        };
+                        if let Ok(Some(name)) = self.resolve_name("move", slug) {
+                            names.lock().unwrap().insert(slug.to_string(), name);
+                        }
+                    }
+                });
+            }
+        });
+        names.into_inner().unwrap()
+    }
+
+    /// 作品1つ分のわざ一覧を整形する。わざ名は日本語に引き、引けなければスラッグのまま
+    pub fn format_moves(&self, learnset: &Learnset, version_group: &str) -> String {
+        let moves = learnset.for_version_group(version_group);
+        let mut slugs: Vec<&str> = moves.iter().map(|m| m.name.as_str()).collect();
+        slugs.sort_unstable();
+        slugs.dedup();
+        let names = self.move_names(&slugs);
+        format_learnset(&moves, version_group, &|slug| {
+            names.get(slug).cloned().unwrap_or_else(|| slug.to_string())
+        })
+    }
+
+    /// info/{resource}/{key}.json を読み、期限切れなら `remote` で取り直す。
+    /// remote の bool が false の結果（名前が引けなかったなど）は保存しない
+    fn fetch_resource<T, F>(&self, resource: &str, key: u32, remote: F) -> Option<T>
+    where
+        T: Clone + Serialize + serde::de::DeserializeOwned,
+        F: FnOnce() -> Option<(T, bool)>,
+    {
+        let path = self
+            .cache_dir
+            .as_ref()
+            .map(|dir| dir.join(resource).join(format!("{}.json", key)));
+        let cached = path
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|content| serde_json::from_slice::<CachedResource<T>>(&content).ok())
+            .filter(|cached| cached.schema_version == INFO_CACHE_SCHEMA_VERSION);
+        if let Some(ref cached) = cached
+            && Utc::now() - cached.fetched_at < chrono::Duration::days(INFO_CACHE_TTL_DAYS)
+        {
+            return Some(cached.value.clone());
+        }
+
+        match remote() {
+            Some((value, complete)) => {
+                if complete {
+                    self.write_resource(resource, key, &value);
+                }
+                Some(value)
+            }
+            None => cached.map(|cached| cached.value),
+        }
+    }
+
+    /// info/{resource}/{key}.json に保存する。失敗しても表示には影響しないので握りつぶす
+    fn write_resource<T: Serialize>(&self, resource: &str, key: u32, value: &T) {
+        if let Some(ref dir) = self.cache_dir {
+            let dir = dir.join(resource);
+            if std::fs::create_dir_all(&dir).is_ok()
+                && let Ok(content) = serde_json::to_vec(&CachedResource {
+                    schema_version: INFO_CACHE_SCHEMA_VERSION,
+                    fetched_at: Utc::now(),
+                    value,
+                })
+            {
+                let _ = write_atomic(&dir.join(format!("{}.json", key)), &content);
+            }
+        }
+    }
+
+    /// /evolution-chain/{id} を取得して平らにする。
+    /// 戻り値の bool は道具名まですべて日本語にできたか（キャッシュしてよいか）
+    fn fetch_evolution_remote(&self, chain_id: u32) -> Option<(EvolutionChain, bool)> {
+        let url = format!("{}/evolution-chain/{}", self.base_url, chain_id);
+        let response = match self.client.get(&url).send() {
+            Ok(r) if r.status().is_success() => r,
+            _ => return None,
+        };
+        let body = response.json::<ChainResponse>().ok()?;
+
+        let mut complete = true;
+        let chain = EvolutionChain::from_link(&body.chain, &mut |slug| match self
+            .resolve_name("item", slug)
+        {
+            Ok(Some(name)) => name,
+            Ok(None) => slug.to_string(),
+            Err(_) => {
+                complete = false;
+                slug.to_string()
+            }
+        });
+        Some((chain, complete))
+    }
+
+    /// /{resource}/{slug}（ability など）の日本語名。かな(ja-Hrkt)を優先し、無ければ ja。
+    /// 引いた名前は cache_dir/{resource}/{slug}.json に保存し、次からはネットワークに出ない。
+    /// 日本語名が無ければ Ok(None)、取得自体に失敗したら Err
+    fn resolve_name(&self, resource: &str, slug: &str) -> Result<Option<String>> {
+        let path = self
+            .cache_dir
+            .as_ref()
+            .map(|dir| dir.join(resource).join(format!("{}.json", slug)));
+        if let Some(ref path) = path
+            && let Ok(content) = std::fs::read(path)
+            && let Ok(cached) = serde_json::from_slice::<CachedName>(&content)
+        {
+            return Ok(Some(cached.name));
+        }
+
+        let url = format!("{}/{}/{}", self.base_url, resource, slug);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .with_context(|| format!("Failed to fetch {} {}", resource, slug))?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to fetch {} {}: HTTP {}",
+                resource,
+                slug,
+                response.status()
+            ));
+        }
+        let body = response
+            .json::<NamesResponse>()
+            .with_context(|| format!("Failed to parse {} response", resource))?;
+        let name = ["ja-Hrkt", "ja"].iter().find_map(|lang| {
+            body.names
+                .iter()
+                .find(|n| n.language.name == *lang)
+                .map(|n| n.name.clone())
+        });
+
+        // 保存に失敗しても表示には影響しないので握りつぶす
+        if let Some(ref name) = name
+            && let Some(ref path) = path
+            && let Some(dir) = path.parent()
+            && std::fs::create_dir_all(dir).is_ok()
+            && let Ok(content) = serde_json::to_vec(&CachedName { name: name.clone() })
+        {
+            let _ = write_atomic(path, &content);
+        }
+        Ok(name)
+    }
+
+    fn cache_path(&self, id: u32) -> Option<PathBuf> {
+        self.cache_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.json", id)))
+    }
+
+    /// 書式の版が違う・壊れているキャッシュは無いものとして扱う
+    fn read_cache(&self, id: u32) -> Option<CachedInfo> {
+        let content = std::fs::read(self.cache_path(id)?).ok()?;
+        let cached: CachedInfo = serde_json::from_slice(&content).ok()?;
+        (cached.schema_version == INFO_CACHE_SCHEMA_VERSION).then_some(cached)
+    }
+
+    /// 保存に失敗しても表示には影響しないので握りつぶす
+    fn write_cache(&self, id: u32, info: &CachedInfo) {
+        if let Some(path) = self.cache_path(id)
+            && let Ok(content) = serde_json::to_vec(info)
+        {
+            let _ = write_atomic(&path, &content);
+        }
     }
 
     #[cfg(test)]
@@ -258,8 +833,46 @@ impl PokemonInfoService {
             client: Client::new(),
             base_url,
             id_map,
+            cache_dir: None,
         }
     }
+
+    #[cfg(test)]
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+}
+
+/// 今の書式で読める情報キャッシュか（cache verify 用）
+#[cfg(feature = "sprites")]
+pub fn is_valid_cache_file(path: &Path) -> bool {
+    std::fs::read(path)
+        .ok()
+        .and_then(|content| serde_json::from_slice::<CachedInfo>(&content).ok())
+        .is_some_and(|cached| cached.schema_version == INFO_CACHE_SCHEMA_VERSION)
+}
+
+/// write_atomic の一時ファイル名を書き込みごとに変えるための通し番号
+#[cfg(feature = "sprites")]
+static WRITE_SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+
+/// 一時ファイルに書いてから置き換える。途中で落ちても壊れたキャッシュを残さない。
+/// 特性・わざの名前は prefetch の複数スレッドが同じ slug を同時に書くことがあるので、
+/// 一時ファイル名はプロセス id に加えて書き込みごとの通し番号で分ける
+#[cfg(feature = "sprites")]
+fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let sequence = WRITE_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let tmp_path = path.with_extension(format!("json.{}.{}.part", std::process::id(), sequence));
+    std::fs::write(&tmp_path, content)
+        .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path).with_context(|| {
+        format!(
+            "Failed to move {} into place at {}",
+            tmp_path.display(),
+            path.display()
+        )
+    })
 }
 
 // --- 表示整形（ネット不要の純粋関数。スプライト下に印字する文字列を組み立てる） ---
@@ -295,13 +908,34 @@ pub fn format_header(id: Option<u32>, japanese: Option<&str>, english: &str) ->
     }
 }
 
-/// タイプ・種族値・説明をまとめた本文。取得できた部分だけを連結する
+/// タイプ・種族値・タイプ相性・説明をまとめた本文。取得できた部分だけを連結する。
+/// `entry_types` は辞書のタイプ（英語スラッグ）で、info が取れなくても（オフラインでも）
+/// タイプと相性はこれで出す
 #[cfg(feature = "sprites")]
-pub fn format_body(info: &PokemonInfo) -> String {
+pub fn format_body(info: Option<&PokemonInfo>, entry_types: &[String]) -> String {
+    let fallback;
+    let types = match info {
+        Some(info) if !info.types.is_empty() => &info.types,
+        _ => {
+            fallback = types_from_slugs(entry_types);
+            &fallback
+        }
+    };
+
     let mut out = String::new();
-    out.push_str(&format_types(&info.types));
-    out.push_str(&format_stats(&info.stats));
-    if let Some(ref description) = info.description {
+    out.push_str(&format_types(types));
+    if let Some(info) = info {
+        out.push_str(&format_profile(info));
+        out.push_str(&format_stats(&info.stats));
+    }
+    // 相性は辞書のタイプで出す。旧辞書でタイプが無ければ取得したもので
+    let slugs: Vec<&str> = if entry_types.is_empty() {
+        types.iter().map(|t| t.slug.as_str()).collect()
+    } else {
+        entry_types.iter().map(String::as_str).collect()
+    };
+    out.push_str(&format_matchups(&slugs));
+    if let Some(description) = info.and_then(|info| info.description.as_ref()) {
         out.push_str(&format!("\n{}\n", description));
     }
     out
@@ -309,15 +943,111 @@ pub fn format_body(info: &PokemonInfo) -> String {
 
 /// タイプ公式カラーの背景色チップを横に並べた1行。空なら空文字
 #[cfg(feature = "sprites")]
-fn format_types(types: &[PokemonType]) -> String {
+pub fn format_types(types: &[PokemonType]) -> String {
     if types.is_empty() {
         return String::new();
     }
-    let chips: Vec<String> = types
+    format!("\n{}\n", format_chips(types))
+}
+
+/// タイプチップを空白区切りで並べる
+#[cfg(feature = "sprites")]
+fn format_chips(types: &[PokemonType]) -> String {
+    types
         .iter()
         .map(|t| format!("{} {} {}", t.color, t.ja, SGR_RESET))
-        .collect();
-    format!("\n{}\n", chips.join(" "))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 受けるダメージの倍率ごとに攻撃タイプのチップを並べる。等倍だけなら空文字
+#[cfg(feature = "sprites")]
+fn format_matchups(slugs: &[&str]) -> String {
+    let matchups = crate::type_chart::matchups(slugs);
+    if matchups.is_empty() {
+        return String::new();
+    }
+    let rows = [
+        ("弱点 ×4", &matchups.quadruple),
+        ("弱点 ×2", &matchups.double),
+        ("耐性 ×½", &matchups.half),
+        ("耐性 ×¼", &matchups.quarter),
+        ("無効 ×0", &matchups.immune),
+    ];
+    let mut out = String::from("\n");
+    for (label, attacking) in rows {
+        if attacking.is_empty() {
+            continue;
+        }
+        out.push_str(&format!(
+            "{}  {}\n",
+            label,
+            format_chips(&types_from_slugs(attacking))
+        ));
+    }
+    out
+}
+
+/// 分類・高さ・重さ・特性・タマゴグループ・性別比。取れた行だけを出し、何も無ければ空文字。
+/// ラベルは種族値と同じく表示幅8桁に揃える
+#[cfg(feature = "sprites")]
+fn format_profile(info: &PokemonInfo) -> String {
+    let mut lines = Vec::new();
+    if let Some(ref genus) = info.genus {
+        lines.push(format!("ぶんるい  {}", genus));
+    }
+    if let Some(height) = info.height {
+        lines.push(format!("たかさ    {:.1} m", height as f64 / 10.0));
+    }
+    if let Some(weight) = info.weight {
+        lines.push(format!("おもさ    {:.1} kg", weight as f64 / 10.0));
+    }
+    if !info.abilities.is_empty() {
+        let names: Vec<String> = info
+            .abilities
+            .iter()
+            .map(|a| {
+                if a.hidden {
+                    format!("{}（かくれ）", a.name)
+                } else {
+                    a.name.clone()
+                }
+            })
+            .collect();
+        lines.push(format!("とくせい  {}", names.join(" / ")));
+    }
+    if !info.egg_groups.is_empty() {
+        lines.push(format!("タマゴ    {}", info.egg_groups.join(" / ")));
+    }
+    if let Some(rate) = info.gender_rate {
+        lines.push(format!("せいべつ  {}", format_gender_rate(rate)));
+    }
+
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!("\n{}\n", lines.join("\n"))
+}
+
+/// メスの割合（8分の何か）を「♂ 87.5% / ♀ 12.5%」に。-1 は性別不明
+#[cfg(feature = "sprites")]
+fn format_gender_rate(rate: i8) -> String {
+    if rate < 0 {
+        return "ふめい".to_string();
+    }
+    let percent = |eighths: i8| {
+        let value = eighths as f64 * 12.5;
+        if value.fract() == 0.0 {
+            format!("{}%", value as u32)
+        } else {
+            format!("{:.1}%", value)
+        }
+    };
+    match rate {
+        0 => "♂ のみ".to_string(),
+        8 => "♀ のみ".to_string(),
+        _ => format!("♂ {} / ♀ {}", percent(8 - rate), percent(rate)),
+    }
 }
 
 /// 種族値ゲージ（各行）と合計。空なら空文字
@@ -340,7 +1070,7 @@ fn format_stats(stats: &[StatEntry]) -> String {
 /// `ラベル  値 ████░░░░` 形式の1行を作る。label は表示幅8桁に揃え済みで、
 /// 値との間には固定スペースを2つ入れる（3桁の値でもラベルと密着させないため）
 #[cfg(feature = "sprites")]
-fn format_stat_gauge(label: &str, value: u16) -> String {
+pub fn format_stat_gauge(label: &str, value: u16) -> String {
     // ここで STAT_MAX にクランプするため filled は必ず GAUGE_WIDTH 以下。
     // この .min(STAT_MAX) を外すと下の GAUGE_WIDTH - filled がアンダーフローする
     let filled = value.min(STAT_MAX) as usize * GAUGE_WIDTH / STAT_MAX as usize;
@@ -360,6 +1090,7 @@ fn format_stat_gauge(label: &str, value: u16) -> String {
 #[cfg(feature = "sprites")]
 mod tests {
     use super::*;
+    use crate::render::strip_sgr;
 
     #[test]
     fn test_format_stat_gauge() {
@@ -403,6 +1134,44 @@ mod tests {
         assert_eq!(format_types(&[]), "");
     }
 
+    #[test]
+    fn test_format_matchups() {
+        let out = strip_sgr(&format_matchups(&["fire", "flying"]));
+        assert!(out.contains("弱点 ×4   いわ \n"));
+        assert!(out.contains("弱点 ×2   みず   でんき \n"));
+        assert!(out.contains("耐性 ×¼   くさ   むし \n"));
+        assert!(out.contains("無効 ×0   じめん \n"));
+        assert_eq!(format_matchups(&[]), "");
+    }
+
+    #[test]
+    fn test_format_body_works_offline_from_entry_types() {
+        let types = vec!["ghost".to_string(), "poison".to_string()];
+        let out = strip_sgr(&format_body(None, &types));
+        // 取得できなくてもタイプと相性は出る
+        assert!(out.contains(" ゴースト   どく "));
+        assert!(out.contains("無効 ×0   ノーマル   かくとう "));
+        assert!(!out.contains("ごうけい"));
+    }
+
+    #[test]
+    fn test_format_body_puts_matchups_below_stats() {
+        let info = PokemonInfo {
+            types: types_from_slugs(&["electric"]),
+            stats: vec![StatEntry {
+                label: "HP      ",
+                value: 35,
+            }],
+            description: Some("説明".to_string()),
+            ..Default::default()
+        };
+        let out = strip_sgr(&format_body(Some(&info), &["electric".to_string()]));
+        let stats = out.find("ごうけい").unwrap();
+        let weak = out.find("弱点 ×2").unwrap();
+        let description = out.find("説明").unwrap();
+        assert!(stats < weak && weak < description);
+    }
+
     #[test]
     fn test_clean_flavor_removes_breaks() {
         assert_eq!(
@@ -505,4 +1274,486 @@ mod tests {
         let info = service.fetch("Pikachu").expect("should fetch");
         assert_eq!(info.description.as_deref(), Some("漢字テキスト"));
     }
+
+    fn pikachu_id_map() -> HashMap<String, u32> {
+        let mut id_map = HashMap::new();
+        id_map.insert("Pikachu".to_string(), 25);
+        id_map
+    }
+
+    fn cached_pikachu(fetched_at: DateTime<Utc>) -> CachedInfo {
+        CachedInfo {
+            schema_version: INFO_CACHE_SCHEMA_VERSION,
+            fetched_at,
+            types: vec!["electric".to_string()],
+            stats: vec![CachedStat {
+                name: "hp".to_string(),
+                base_stat: 35,
+            }],
+            description: Some("キャッシュ".to_string()),
+            abilities: vec![CachedAbility {
+                name: "せいでんき".to_string(),
+                hidden: false,
+            }],
+            height: Some(4),
+            weight: Some(60),
+            genus: Some("ねずみポケモン".to_string()),
+            egg_groups: vec!["ground".to_string()],
+            gender_rate: Some(4),
+            evolution_chain_id: Some(10),
+        }
+    }
+
+    #[test]
+    fn test_fetch_writes_cache_and_reuses_it() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/pokemon/25");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"types":[{"slot":1,"type":{"name":"electric"}}],"stats":[]}"#);
+        });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test(server.url(""), pikachu_id_map())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+
+        assert_eq!(service.fetch("Pikachu").unwrap().types[0].ja, "でんき");
+        assert!(temp_dir.path().join("25.json").exists());
+
+        // 2回目はキャッシュから。リクエストは増えない
+        assert_eq!(service.fetch("Pikachu").unwrap().types[0].ja, "でんき");
+        mock.assert_hits(1);
+    }
+
+    #[test]
+    fn test_fetch_uses_stale_cache_when_offline() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        // 接続できないアドレス
+        let service =
+            PokemonInfoService::for_test("http://127.0.0.1:9".to_string(), pikachu_id_map())
+                .with_cache_dir(temp_dir.path().to_path_buf());
+        let stale = cached_pikachu(Utc::now() - chrono::Duration::days(INFO_CACHE_TTL_DAYS + 1));
+        service.write_cache(25, &stale);
+
+        let info = service.fetch("Pikachu").expect("should fall back to cache");
+        assert_eq!(info.description.as_deref(), Some("キャッシュ"));
+        assert_eq!(info.stats[0].value, 35);
+    }
+
+    #[test]
+    fn test_fetch_refreshes_expired_cache() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/pokemon/25");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"types":[{"slot":1,"type":{"name":"fairy"}}],"stats":[]}"#);
+        });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test(server.url(""), pikachu_id_map())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+        let stale = cached_pikachu(Utc::now() - chrono::Duration::days(INFO_CACHE_TTL_DAYS + 1));
+        service.write_cache(25, &stale);
+
+        assert_eq!(service.fetch("Pikachu").unwrap().types[0].ja, "フェアリー");
+        mock.assert_hits(1);
+        // 取り直した結果で上書きされている
+        assert_eq!(service.read_cache(25).unwrap().types, vec!["fairy"]);
+    }
+
+    #[test]
+    fn test_is_valid_cache_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test("test://mock".to_string(), HashMap::new())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+        service.write_cache(25, &cached_pikachu(Utc::now()));
+        std::fs::write(temp_dir.path().join("26.json"), b"{broken").unwrap();
+
+        assert!(is_valid_cache_file(&temp_dir.path().join("25.json")));
+        assert!(!is_valid_cache_file(&temp_dir.path().join("26.json")));
+    }
+
+    #[test]
+    fn test_write_atomic_from_many_threads() {
+        // 同じ slug を複数スレッドが同時に書いても、一時ファイルがぶつからない
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("static.json");
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let path = &path;
+                scope.spawn(move || {
+                    for _ in 0..20 {
+                        write_atomic(path, format!("{{\"n\":{}}}", i).as_bytes()).unwrap();
+                    }
+                });
+            }
+        });
+
+        let written: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert!(written["n"].is_u64());
+        let leftovers = std::fs::read_dir(temp_dir.path()).unwrap().count();
+        assert_eq!(leftovers, 1);
+    }
+
+    #[test]
+    fn test_read_cache_ignores_other_schema_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test("test://mock".to_string(), pikachu_id_map())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+
+        let mut cached = cached_pikachu(Utc::now());
+        service.write_cache(25, &cached);
+        assert_eq!(service.read_cache(25), Some(cached.clone()));
+
+        cached.schema_version = INFO_CACHE_SCHEMA_VERSION + 1;
+        service.write_cache(25, &cached);
+        assert_eq!(service.read_cache(25), None);
+    }
+
+    #[test]
+    fn test_prefetch_fills_cache_once() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/pokemon/25");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"types":[],"stats":[]}"#);
+        });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test(server.url(""), HashMap::new())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+
+        // id_map に無くても id で取れる
+        assert!(service.prefetch(25).unwrap());
+        assert!(!service.prefetch(25).unwrap());
+        mock.assert_hits(1);
+    }
+
+    #[test]
+    fn test_format_profile() {
+        let info = PokemonInfo {
+            genus: Some("ねずみポケモン".to_string()),
+            height: Some(4),
+            weight: Some(60),
+            abilities: vec![
+                Ability {
+                    name: "せいでんき".to_string(),
+                    hidden: false,
+                },
+                Ability {
+                    name: "ひらいしん".to_string(),
+                    hidden: true,
+                },
+            ],
+            egg_groups: vec!["りくじょう".to_string(), "ようせい".to_string()],
+            gender_rate: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_profile(&info),
+            "\nぶんるい  ねずみポケモン\n\
+             たかさ    0.4 m\n\
+             おもさ    6.0 kg\n\
+             とくせい  せいでんき / ひらいしん（かくれ）\n\
+             タマゴ    りくじょう / ようせい\n\
+             せいべつ  ♂ 50% / ♀ 50%\n"
+        );
+        assert_eq!(format_profile(&PokemonInfo::default()), "");
+    }
+
+    #[test]
+    fn test_format_gender_rate() {
+        assert_eq!(format_gender_rate(-1), "ふめい");
+        assert_eq!(format_gender_rate(0), "♂ のみ");
+        assert_eq!(format_gender_rate(1), "♂ 87.5% / ♀ 12.5%");
+        assert_eq!(format_gender_rate(8), "♀ のみ");
+    }
+
+    #[test]
+    fn test_fetch_resolves_profile_and_caches_ability_names() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let species_url = server.url("/pokemon-species/25");
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon/25");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(format!(
+                    r#"{{"types":[],"stats":[],"height":4,"weight":60,
+                        "abilities":[
+                            {{"ability":{{"name":"static"}},"is_hidden":false,"slot":1}},
+                            {{"ability":{{"name":"lightning-rod"}},"is_hidden":true,"slot":3}}
+                        ],
+                        "species":{{"url":"{}"}}}}"#,
+                    species_url
+                ));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon-species/25");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(
+                    r#"{"flavor_text_entries":[],
+                        "genera":[
+                            {"genus":"Mouse Pokémon","language":{"name":"en"}},
+                            {"genus":"ねずみポケモン","language":{"name":"ja"}}
+                        ],
+                        "egg_groups":[{"name":"ground"},{"name":"fairy"}],
+                        "gender_rate":4,
+                        "evolution_chain":{"url":"https://pokeapi.co/api/v2/evolution-chain/10/"}}"#,
+                );
+        });
+        let static_mock = server.mock(|when, then| {
+            when.method(GET).path("/ability/static");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(
+                    r#"{"names":[
+                        {"name":"静電気","language":{"name":"ja"}},
+                        {"name":"せいでんき","language":{"name":"ja-Hrkt"}}
+                    ]}"#,
+                );
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/ability/lightning-rod");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"names":[{"name":"ひらいしん","language":{"name":"ja-Hrkt"}}]}"#);
+        });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test(server.url(""), pikachu_id_map())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+
+        let info = service.fetch("Pikachu").expect("should fetch");
+        assert_eq!(info.genus.as_deref(), Some("ねずみポケモン"));
+        assert_eq!((info.height, info.weight), (Some(4), Some(60)));
+        assert_eq!(info.abilities[0].name, "せいでんき");
+        assert!(!info.abilities[0].hidden);
+        assert_eq!(info.abilities[1].name, "ひらいしん");
+        assert!(info.abilities[1].hidden);
+        assert_eq!(info.egg_groups, ["りくじょう", "ようせい"]);
+        assert_eq!(info.gender_rate, Some(4));
+        assert_eq!(info.evolution_chain_id, Some(10));
+
+        // 特性名は個別にキャッシュされ、別のポケモンでも使い回す
+        assert!(temp_dir.path().join("ability").join("static.json").exists());
+        assert_eq!(
+            service
+                .resolve_name("ability", "static")
+                .unwrap()
+                .as_deref(),
+            Some("せいでんき")
+        );
+        static_mock.assert_hits(1);
+    }
+
+    #[test]
+    fn test_fetch_evolution_chain_resolves_items_and_caches() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let chain_mock = server.mock(|when, then| {
+            when.method(GET).path("/evolution-chain/10");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(
+                    r#"{"chain":{"species":{"url":"https://pokeapi.co/api/v2/pokemon-species/172/"},
+                        "evolves_to":[{"species":{"url":"https://pokeapi.co/api/v2/pokemon-species/25/"},
+                            "evolution_details":[{"trigger":{"name":"level-up"},"min_happiness":220}],
+                            "evolves_to":[{"species":{"url":"https://pokeapi.co/api/v2/pokemon-species/26/"},
+                                "evolution_details":[{"trigger":{"name":"use-item"},"item":{"name":"thunder-stone"}}]}]}]}}"#,
+                );
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/item/thunder-stone");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"names":[{"name":"かみなりのいし","language":{"name":"ja-Hrkt"}}]}"#);
+        });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test(server.url(""), pikachu_id_map())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+
+        let chain = service.fetch_evolution_chain(10).expect("should fetch");
+        let ids: Vec<u32> = chain.nodes.iter().map(|n| n.species_id).collect();
+        assert_eq!(ids, [172, 25, 26]);
+        assert_eq!(chain.nodes[2].condition.as_deref(), Some("かみなりのいし"));
+
+        // 2回目はキャッシュから
+        assert_eq!(service.fetch_evolution_chain(10), Some(chain));
+        chain_mock.assert_hits(1);
+        assert!(
+            temp_dir
+                .path()
+                .join("evolution-chain")
+                .join("10.json")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_fetch_learnset_and_move_names() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let pokemon_mock = server.mock(|when, then| {
+            when.method(GET).path("/pokemon/25");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(
+                    r#"{"types":[],"stats":[],"moves":[
+                        {"move":{"name":"thunderbolt"},"version_group_details":[
+                            {"level_learned_at":0,"move_learn_method":{"name":"machine"},"version_group":{"name":"x-y"}}
+                        ]},
+                        {"move":{"name":"thunder-shock"},"version_group_details":[
+                            {"level_learned_at":1,"move_learn_method":{"name":"level-up"},"version_group":{"name":"x-y"}}
+                        ]}
+                    ]}"#,
+                );
+        });
+        let move_mock = server.mock(|when, then| {
+            when.method(GET).path("/move/thunderbolt");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"names":[{"name":"10まんボルト","language":{"name":"ja-Hrkt"}}]}"#);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/move/thunder-shock");
+            then.status(404);
+        });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test(server.url(""), pikachu_id_map())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+
+        let learnset = service.fetch_learnset("Pikachu").expect("should fetch");
+        assert_eq!(learnset.version_groups(), ["x-y"]);
+        // 2回目はキャッシュから
+        assert_eq!(service.fetch_learnset("Pikachu"), Some(learnset.clone()));
+        pokemon_mock.assert_hits(1);
+
+        // 引けなかったわざはスラッグのまま
+        let out = service.format_moves(&learnset, "x-y");
+        assert!(out.contains("  Lv.  1  thunder-shock\n"));
+        assert!(out.contains("  10まんボルト\n"));
+        service.format_moves(&learnset, "x-y");
+        move_mock.assert_hits(1);
+        assert!(
+            temp_dir
+                .path()
+                .join("move")
+                .join("thunderbolt.json")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_fetch_keeps_learnset_from_the_same_response() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let pokemon_mock = server.mock(|when, then| {
+            when.method(GET).path("/pokemon/25");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(
+                    r#"{"types":[],"stats":[],"moves":[
+                        {"move":{"name":"thunderbolt"},"version_group_details":[
+                            {"level_learned_at":0,"move_learn_method":{"name":"machine"},"version_group":{"name":"x-y"}}
+                        ]}
+                    ]}"#,
+                );
+        });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test(server.url(""), pikachu_id_map())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+
+        assert!(service.fetch("Pikachu").is_some());
+        // 確認画面で情報を取ったあとの [m] は /pokemon/{id} を取り直さない
+        let learnset = service.fetch_learnset("Pikachu").expect("should be cached");
+        assert_eq!(learnset.version_groups(), ["x-y"]);
+        pokemon_mock.assert_hits(1);
+    }
+
+    #[test]
+    fn test_fetch_evolution_chain_unavailable() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/evolution-chain/10");
+            then.status(500);
+        });
+        let service = PokemonInfoService::for_test(server.url(""), pikachu_id_map());
+        assert!(service.fetch_evolution_chain(10).is_none());
+    }
+
+    #[test]
+    fn test_fetch_does_not_cache_when_ability_lookup_failed() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon/25");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"types":[],"stats":[],"abilities":[{"ability":{"name":"static"},"is_hidden":false}]}"#);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/ability/static");
+            then.status(500);
+        });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test(server.url(""), pikachu_id_map())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+
+        // スラッグのまま表示し、次回また引けるよう保存しない
+        let info = service.fetch("Pikachu").expect("should still show info");
+        assert_eq!(info.abilities[0].name, "static");
+        assert!(!temp_dir.path().join("25.json").exists());
+    }
+
+    #[test]
+    fn test_fetch_does_not_cache_when_description_failed() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let species_url = server.url("/pokemon-species/25");
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon/25");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(format!(
+                    r#"{{"types":[],"stats":[],"species":{{"url":"{}"}}}}"#,
+                    species_url
+                ));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon-species/25");
+            then.status(500);
+        });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let service = PokemonInfoService::for_test(server.url(""), pikachu_id_map())
+            .with_cache_dir(temp_dir.path().to_path_buf());
+
+        let info = service.fetch("Pikachu").expect("should still show info");
+        assert!(info.description.is_none());
+        assert!(!temp_dir.path().join("25.json").exists());
+    }
 }
@@ -1,15 +1,24 @@
 #[cfg(feature = "cries")]
 use crate::cry::CryService;
+use crate::data::DataLoader;
+#[cfg(feature = "sprites")]
+use crate::evolution::{Direction, EvolutionChain};
 #[cfg(feature = "sprites")]
 use crate::info::PokemonInfoService;
+use crate::language::Language;
+#[cfg(feature = "sprites")]
+use crate::learnset::version_group_ja;
+use crate::models::FormCategory;
 use crate::search::SearchService;
 #[cfg(feature = "sprites")]
-use crate::sprite::SpriteService;
+use crate::sprite::{SpriteAnimation, SpriteRenderer, SpriteService, SpriteVariant, read_key};
 use anyhow::{Context, Result};
 #[cfg(feature = "sprites")]
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
-    terminal::{disable_raw_mode, enable_raw_mode},
+    cursor::{MoveToColumn, MoveUp},
+    event::KeyCode,
+    execute,
+    terminal::{Clear, ClearType, disable_raw_mode, enable_raw_mode},
 };
 use skim::prelude::*;
 #[cfg(feature = "sprites")]
@@ -25,6 +34,8 @@ struct PokemonItem {
     display: String,
     /// skim のマッチ対象。display にローマ字を足したもので、ローマ字は表示されない
     match_text: String,
+    /// フォルムならその種類（プレビューに出す）
+    form_category: Option<FormCategory>,
 }
 
 impl SkimItem for PokemonItem {
@@ -70,35 +81,78 @@ impl SkimItem for PokemonItem {
     }
 
     fn preview(&self, _context: PreviewContext) -> ItemPreview {
-        ItemPreview::Text(format!("日本語: {}\n英語: {}", self.japanese, self.english))
+        let mut text = format!("日本語: {}\n英語: {}", self.japanese, self.english);
+        if let Some(category) = self.form_category {
+            text.push_str(&format!("\nフォルム: {}", category.label()));
+        }
+        ItemPreview::Text(text)
     }
 }
 
 impl PokemonItem {
+    #[cfg(test)]
     fn new(ja: &str, en: &str, type_tokens: &str) -> Self {
-        let display = format!("{} → {}", ja, en);
+        Self::with_label(ja, en, ja, en, type_tokens)
+    }
+
+    /// `source` → `target` の形で表示する（--from / --to の言語の名前）。
+    /// 確定時に返すのは言語によらず英名（--output は呼び出し側の print_record で効かせる）
+    fn with_label(ja: &str, en: &str, source: &str, target: &str, type_tokens: &str) -> Self {
+        let display = format!("{} → {}", source, target);
         let romaji = crate::romaji::variants(ja).join(" ");
-        // タイプはローマ字と同じく match_text にだけ載せる隠しトークン。
-        // 空のときに末尾空白を足さないよう分岐する
-        let match_text = if type_tokens.is_empty() {
-            format!("{} {}", display, romaji)
-        } else {
-            format!("{} {} {}", display, romaji, type_tokens)
-        };
+        // 漢字表記やほかの言語で表示していても、カタカナとローマ字で絞り込めるよう
+        // 日本語名を隠しトークンに足す。タイプも match_text にだけ載せる隠しトークン。
+        // 空のときに末尾空白を足さないよう空の要素は除く
+        let hidden_ja = if display.contains(ja) { "" } else { ja };
+        let match_text = [display.as_str(), hidden_ja, &romaji, type_tokens]
+            .iter()
+            .filter(|token| !token.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
         Self {
             japanese: ja.to_string(),
             english: en.to_string(),
             match_text,
             display,
+            form_category: None,
+        }
+    }
+
+    /// --forms collapse でまとめたフォルムの数を「[+N]」として表示に足す。
+    /// match_text の先頭は display のままにしておく（ハイライト位置がずれないように）
+    fn with_form_count(mut self, count: usize) -> Self {
+        if count > 0 {
+            let badge = format!(" [+{}]", count);
+            self.match_text.insert_str(self.display.len(), &badge);
+            self.display.push_str(&badge);
         }
+        self
+    }
+
+    fn with_form_category(mut self, category: Option<FormCategory>) -> Self {
+        self.form_category = category;
+        self
     }
 }
 
 /// インタラクティブ選択機能
 pub struct InteractiveSelector {
     search_service: SearchService,
+    /// 一覧に並べる名前の言語（検索元 → 出力先）
+    from: Language,
+    to: Language,
     #[cfg(feature = "sprites")]
     sprite_service: Option<SpriteService>,
+    /// 確認画面で最初に表示するスプライトの種類
+    #[cfg(feature = "sprites")]
+    sprite_variant: SpriteVariant,
+    /// 確認画面で動くスプライトを再生するか
+    #[cfg(feature = "sprites")]
+    animate: bool,
+    /// 確認画面の [m] で表示するわざの作品。None なら最新作
+    #[cfg(feature = "sprites")]
+    version_group: Option<String>,
     #[cfg(feature = "sprites")]
     info_service: Option<PokemonInfoService>,
     #[cfg(feature = "cries")]
@@ -110,37 +164,92 @@ impl InteractiveSelector {
     pub fn new(search_service: SearchService) -> Self {
         Self {
             search_service,
+            from: Language::Ja,
+            to: Language::En,
             #[cfg(feature = "sprites")]
             sprite_service: None,
             #[cfg(feature = "sprites")]
+            sprite_variant: SpriteVariant::Default,
+            #[cfg(feature = "sprites")]
+            animate: false,
+            #[cfg(feature = "sprites")]
+            version_group: None,
+            #[cfg(feature = "sprites")]
             info_service: None,
             #[cfg(feature = "cries")]
             cry_service: None,
         }
     }
 
-    /// info は対話選択中のスプライト表示でしか使わないため sprite と同時にゲートする
+    /// 一覧に並べる名前の言語。検索そのものは SearchService の検索元の言語で行う
+    pub fn languages(mut self, from: Language, to: Language) -> Self {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    /// info は対話選択中のスプライト表示でしか使わないため sprite と同時にゲートする。
+    /// id は `loader` の辞書（--dict）から引く
     #[cfg_attr(not(feature = "sprites"), allow(unused_mut, unused_variables))]
-    pub fn show_sprite(mut self, enabled: bool) -> Self {
+    pub fn show_sprite(mut self, enabled: bool, loader: &DataLoader) -> Self {
         #[cfg(feature = "sprites")]
         if enabled {
             // 初期化失敗（画像表示不可の端末など）は None のまま握りつぶす。
             // -s でも表示できないだけで、選択自体は続行させる
-            self.sprite_service = SpriteService::new().ok();
-            self.info_service = PokemonInfoService::new().ok();
+            self.sprite_service = SpriteService::with_loader(loader).ok();
+            self.info_service = PokemonInfoService::with_loader(loader).ok();
+        }
+        self
+    }
+
+    /// 確認画面で最初に表示するスプライトの種類（[v] で切り替えられる）
+    #[cfg(feature = "sprites")]
+    pub fn sprite_variant(mut self, variant: SpriteVariant) -> Self {
+        self.sprite_variant = variant;
+        self
+    }
+
+    /// スプライトの表示サイズ（文字数）。show_sprite の後に呼ぶ
+    #[cfg(feature = "sprites")]
+    pub fn sprite_size(mut self, width: Option<u32>, height: Option<u32>) -> Self {
+        if let Some(ref mut sprite_service) = self.sprite_service {
+            sprite_service.set_size(width, height);
+        }
+        self
+    }
+
+    /// 静止画の描き方。show_sprite の後に呼ぶ
+    #[cfg(feature = "sprites")]
+    pub fn sprite_renderer(mut self, renderer: SpriteRenderer) -> Self {
+        if let Some(ref mut sprite_service) = self.sprite_service {
+            sprite_service.set_renderer(renderer);
         }
         self
     }
 
+    /// 確認画面で動くスプライトがあれば再生する（無ければ静止画）
+    #[cfg(feature = "sprites")]
+    pub fn animate(mut self, enabled: bool) -> Self {
+        self.animate = enabled;
+        self
+    }
+
+    /// 確認画面の [m] で表示するわざの作品（scarlet-violet など）
+    #[cfg(feature = "sprites")]
+    pub fn version_group(mut self, version_group: Option<String>) -> Self {
+        self.version_group = version_group;
+        self
+    }
+
     /// 有効時のみ CryService を初期化する（辞書の再読み込みを避けるため）
     #[cfg_attr(not(feature = "cries"), allow(unused_mut, unused_variables))]
-    pub fn play_cry(mut self, enabled: bool) -> Self {
+    pub fn play_cry(mut self, enabled: bool, loader: &DataLoader) -> Self {
         #[cfg(feature = "cries")]
         {
             self.cry_service = if enabled {
                 // -c は明示的な要求なので、鳴らない理由は伝える。
                 // stdout はパイプライン連携のために汚さない
-                match CryService::new() {
+                match CryService::with_loader(loader) {
                     Ok(service) => Some(service),
                     Err(e) => {
                         eprintln!("鳴き声を初期化できませんでした: {:#}", e);
@@ -197,6 +306,43 @@ impl InteractiveSelector {
         }
     }
 
+    /// 対話選択を開かずに1件に決める（--best）。
+    /// 完全一致がなければ綴り違いの最上位候補を採り、読み替えたことを標準エラーに出す
+    pub fn select_best(&self, query: &str) -> Option<String> {
+        let entry = self.search_service.best_match(query)?;
+        if self.search_service.entry_exact(query).is_none() {
+            eprintln!("「{}」を {} として扱います", query, entry.ja);
+        }
+        let english_name = entry.en.clone();
+        #[cfg(feature = "cries")]
+        self.play_cry_if_enabled(&english_name);
+        Some(english_name)
+    }
+
+    /// 英名から逆引きのインタラクティブ選択
+    /// 戻り値は select_interactive と同じく確定した英名（表記は辞書の正規形）。
+    /// 日本語名への変換は呼び出し側で SearchService::search_exact_en を使う
+    pub fn select_interactive_reverse(&self, query: &str) -> Result<Option<String>> {
+        // 大文字小文字を無視した完全一致なら skim を開かない
+        if let Some(japanese) = self.search_service.search_exact_en(query) {
+            let english_name = self
+                .search_service
+                .entry(japanese)
+                .map_or(query, |entry| entry.en.as_str())
+                .to_string();
+            #[cfg(feature = "cries")]
+            self.play_cry_if_enabled(&english_name);
+            return Ok(Some(english_name));
+        }
+
+        let partial_matches = self.search_service.search_partial_en(query);
+
+        match partial_matches.len() {
+            0 => Ok(None),
+            _ => self.run_skim_selection(&partial_matches, query),
+        }
+    }
+
     /// 全候補からインタラクティブ選択（空クエリ時）
     #[allow(dead_code)] // CLIインターフェースで使用予定
     pub fn select_from_all(&self) -> Result<Option<String>> {
@@ -210,15 +356,40 @@ impl InteractiveSelector {
         candidates: &[(&str, &str)],
         initial_query: &str,
     ) -> Result<Option<String>> {
+        // --forms collapse で種だけを並べた一覧か（フォルムの選択に進む）
+        let collapsed = self.search_service.collapses_forms()
+            && !candidates
+                .iter()
+                .any(|(ja, _)| self.search_service.is_form(ja));
+
         // skim用のアイテムを作成
         let items: Vec<Arc<dyn SkimItem>> = candidates
             .iter()
             .map(|(ja, en)| {
                 let type_tokens = self.search_service.type_tokens(ja);
-                Arc::new(PokemonItem::new(ja, en, &type_tokens)) as Arc<dyn SkimItem>
+                let source = self.search_service.name_in(ja, self.from).unwrap_or(ja);
+                let target = self.search_service.name_in(ja, self.to).unwrap_or(en);
+                let form_count = if collapsed {
+                    self.search_service.forms_of(ja).len().saturating_sub(1)
+                } else {
+                    0
+                };
+                let entry = self.search_service.entry(ja);
+                Arc::new(
+                    PokemonItem::with_label(ja, en, source, target, &type_tokens)
+                        .with_form_count(form_count)
+                        .with_form_category(entry.and_then(|entry| entry.form_category)),
+                ) as Arc<dyn SkimItem>
             })
             .collect();
 
+        // 日本語以外から引くとき（-r だけで起動した場合など）は入力の言語をプロンプトに出す
+        let prompt = if self.from == Language::Ja {
+            "ポケモンを選択: ".to_string()
+        } else {
+            format!("ポケモンを選択（{}）: ", self.from.arg_name())
+        };
+
         // skimオプションを設定
         let options = SkimOptionsBuilder::default()
             .height(Some("40%"))
@@ -231,7 +402,7 @@ impl InteractiveSelector {
             .preview(Some(""))
             .preview_window(Some("down:3:wrap"))
             .query(Some(initial_query))
-            .prompt(Some("ポケモンを選択: "))
+            .prompt(Some(&prompt))
             // ctrl-d / ctrl-u は skim 既定の delete-char-EOF / 行削除を潰して
             // 半ページ送りに充てる。矢印や PageUp/PageDown を使わずに送りたいため
             .bind(vec![
@@ -265,6 +436,22 @@ impl InteractiveSelector {
         if let Some(item) = selected_items.selected_items.first() {
             let english_name = item.output().to_string();
 
+            // まとめた種を選んだら、種とそのフォルムからもう一度選ぶ。
+            // そこで ESC なら種の一覧に戻る
+            if collapsed {
+                let forms = self
+                    .search_service
+                    .search_exact_en(&english_name)
+                    .map(|ja| self.search_service.forms_of(ja))
+                    .unwrap_or_default();
+                if !forms.is_empty() {
+                    return match self.run_skim_selection(&forms, "")? {
+                        Some(selection) => Ok(Some(selection)),
+                        None => self.run_skim_selection(candidates, initial_query),
+                    };
+                }
+            }
+
             // 鳴き声を先に再生。スプライト表示は Enter/ESC 待ちでブロックするため、
             // その後に鳴らすと確定するまで音が出ない
             #[cfg(feature = "cries")]
@@ -292,7 +479,8 @@ impl InteractiveSelector {
         Ok(None)
     }
 
-    /// スプライトを表示して、ESC/ENTER/SPACEでナビゲーション
+    /// スプライトを表示して、ESC/ENTER/SPACE/v/矢印 でナビゲーション。
+    /// 矢印で進化前後・分岐に移ると下に描き直し、Enter ではそのとき表示中のポケモンで確定する
     #[cfg(feature = "sprites")]
     fn show_sprite_with_navigation(
         &self,
@@ -301,64 +489,253 @@ impl InteractiveSelector {
         candidates: &[(&str, &str)],
         _initial_query: &str,
     ) -> Result<Option<String>> {
-        // スプライトを表示
-        sprite_service.display_sprite_for_pokemon(english_name)?;
+        let mut current = english_name.to_string();
+        let mut variant = self.sprite_variant;
+        let mut view = self.draw_confirmation(&current, variant, sprite_service, candidates)?;
+
+        // raw modeを有効化してキー入力を待つ
+        enable_raw_mode()?;
+
+        let result = loop {
+            // Press のみを扱う。Repeat / Release にマッチすると
+            // 押しっぱなしや離した際に鳴らし直し・確定が誤発火する（issue #18）
+            let key = match view.animation {
+                Some(ref mut animation) => animation.next_key(),
+                None => read_key(),
+            };
+            let code = match key {
+                Ok(key) => key.code,
+                Err(e) => {
+                    disable_raw_mode()?;
+                    return Err(e);
+                }
+            };
+            match code {
+                KeyCode::Enter => {
+                    disable_raw_mode()?;
+                    break Some(current);
+                }
+                KeyCode::Esc => {
+                    disable_raw_mode()?;
+                    println!("\n🔄 再選択します...");
+                    break None;
+                }
+                // 画面はそのままに鳴らし直す。play_cry_for_pokemon が
+                // 前の再生を止めるので、連打しても音は重ならない
+                #[cfg(feature = "cries")]
+                KeyCode::Char(' ') => self.play_cry_if_enabled(&current),
+                // 確認画面の先頭まで戻って消し、次の種類で描き直す。
+                // 印字は raw mode だと改行が崩れるので一旦抜ける。前のアニメーションはそこで止める
+                KeyCode::Char('v') => {
+                    variant = variant.next();
+                    disable_raw_mode()?;
+                    execute!(
+                        io::stdout(),
+                        MoveUp(view.lines),
+                        MoveToColumn(0),
+                        Clear(ClearType::FromCursorDown)
+                    )?;
+                    view = self.draw_confirmation(&current, variant, sprite_service, candidates)?;
+                    enable_raw_mode()?;
+                }
+                // わざ一覧を下に描き足す。画像は流れてしまうのでアニメーションは止める
+                KeyCode::Char('m') => {
+                    disable_raw_mode()?;
+                    let moves = self.moves_text(&current);
+                    println!();
+                    print!("{}", moves);
+                    println!("{}", view.hint);
+                    io::stdout().flush()?;
+                    view.lines += 2 + moves.matches('\n').count() as u16;
+                    view.animation = None;
+                    enable_raw_mode()?;
+                }
+                // 進化前後・分岐へ移って全体を下に描き直す。行き止まりなら何もしない
+                KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down => {
+                    let direction = match code {
+                        KeyCode::Left => Direction::Back,
+                        KeyCode::Right => Direction::Forward,
+                        KeyCode::Up => Direction::Previous,
+                        _ => Direction::Next,
+                    };
+                    let Some(next) = self.evolution_target(&view, direction) else {
+                        continue;
+                    };
+                    disable_raw_mode()?;
+                    println!();
+                    current = next;
+                    #[cfg(feature = "cries")]
+                    self.play_cry_if_enabled(&current);
+                    view = self.draw_confirmation(&current, variant, sprite_service, candidates)?;
+                    enable_raw_mode()?;
+                }
+                _ => {}
+            }
+        };
 
-        // 日本語名は候補（ja, en）から英名一致で引く
+        Ok(result)
+    }
+
+    /// 確認画面（スプライト・情報・進化の系統・操作案内）を印字する
+    #[cfg(feature = "sprites")]
+    fn draw_confirmation(
+        &self,
+        english_name: &str,
+        variant: SpriteVariant,
+        sprite_service: &SpriteService,
+        candidates: &[(&str, &str)],
+    ) -> Result<ConfirmationView> {
+        let displayed =
+            sprite_service.display_variant_for_pokemon(english_name, variant, self.animate)?;
+
+        // 日本語名は候補（ja, en）から英名一致で引く。
+        // 進化で候補の外に出たときは辞書から引く
         let japanese = candidates
             .iter()
             .find(|(_, en)| *en == english_name)
-            .map(|(ja, _)| *ja);
+            .map(|(ja, _)| *ja)
+            .or_else(|| self.search_service.search_exact_en(english_name));
 
         // 画像の下に情報を表示。整形は info モジュールに委ね、ここは印字するだけ。
-        // 名前は必ず出す（番号は取れたときだけ添える）。タイプ・種族値・説明は取得成功時のみ
+        // 名前は必ず出す（番号は取れたときだけ添える）。種族値・説明は取得成功時のみ
+        let mut below = String::new();
+        if variant != SpriteVariant::Default {
+            below.push_str(&variant_line(
+                variant,
+                displayed.as_ref().map(|d| d.variant),
+            ));
+            below.push('\n');
+        }
         let id = self
             .info_service
             .as_ref()
             .and_then(|s| s.get_pokemon_id(english_name));
-        print!("{}", crate::info::format_header(id, japanese, english_name));
-        if let Some(ref info_service) = self.info_service
-            && let Some(info) = info_service.fetch(english_name)
-        {
-            print!("{}", crate::info::format_body(&info));
+        below.push_str(&crate::info::format_header(id, japanese, english_name));
+        // タイプ相性は辞書のタイプから出すので、取得できなくても表示される
+        let info = self
+            .info_service
+            .as_ref()
+            .and_then(|s| s.fetch(english_name));
+        let entry_types = japanese
+            .and_then(|ja| self.search_service.entry(ja))
+            .map_or(&[][..], |entry| entry.types.as_slice());
+        below.push_str(&crate::info::format_body(info.as_ref(), entry_types));
+
+        // 進化しないポケモンは系統を出さない。フォルムは元の種の位置に ▶ を付ける
+        let evolution = info
+            .as_ref()
+            .and_then(|info| info.evolution_chain_id)
+            .and_then(|chain_id| self.info_service.as_ref()?.fetch_evolution_chain(chain_id))
+            .filter(|chain| !chain.is_single())
+            .map(|chain| {
+                let position = japanese
+                    .and_then(|ja| self.search_service.species_id(ja))
+                    .and_then(|species_id| chain.position(species_id));
+                (chain, position)
+            });
+        if let Some((ref chain, position)) = evolution {
+            below.push_str("\nしんか\n");
+            below.push_str(&chain.format_tree(position, &|species_id| {
+                self.search_service
+                    .entry_by_id(species_id)
+                    .map_or_else(|| format!("No.{}", species_id), |entry| entry.ja.clone())
+            }));
         }
 
         // ナビゲーション指示を表示（名前は上の見出しで出しているので省く）
-        println!("\n   [Enter] 確定  [ESC] 再選択");
+        let hint = navigation_hint(evolution.as_ref().is_some_and(|(_, p)| p.is_some()));
+        below.push('\n');
+        below.push_str(&hint);
+        println!("{}", below);
         io::stdout().flush()?;
 
-        // raw modeを有効化してキー入力を待つ
-        enable_raw_mode()?;
+        // アニメーションは画像の下に印字した行の分も戻って描き直す
+        let below_lines = below.matches('\n').count() + 1;
+        let height = displayed.as_ref().map_or(0, |d| d.height);
+        let mut animation = displayed.and_then(|d| d.animation);
+        if let Some(ref mut animation) = animation {
+            animation.add_lines_below(below_lines);
+        }
 
-        let result = loop {
-            // Press のみを扱う。Repeat / Release にマッチすると
-            // 押しっぱなしや離した際に鳴らし直し・確定が誤発火する（issue #18）
-            if let Event::Key(KeyEvent {
-                code,
-                kind: KeyEventKind::Press,
-                ..
-            }) = event::read()?
-            {
-                match code {
-                    KeyCode::Enter => {
-                        disable_raw_mode()?;
-                        break Some(english_name.to_string());
-                    }
-                    KeyCode::Esc => {
-                        disable_raw_mode()?;
-                        println!("\n🔄 再選択します...");
-                        break None;
-                    }
-                    // 画面はそのままに鳴らし直す。play_cry_for_pokemon が
-                    // 前の再生を止めるので、連打しても音は重ならない
-                    #[cfg(feature = "cries")]
-                    KeyCode::Char(' ') => self.play_cry_if_enabled(english_name),
-                    _ => {}
-                }
-            }
+        Ok(ConfirmationView {
+            animation,
+            evolution,
+            hint,
+            lines: height + below_lines as u16,
+        })
+    }
+
+    /// 覚えるわざを作品1つ分整形する（改行で終わる）。取れなければその旨だけ
+    #[cfg(feature = "sprites")]
+    fn moves_text(&self, english_name: &str) -> String {
+        let Some(ref info_service) = self.info_service else {
+            return String::new();
+        };
+        let Some(learnset) = info_service.fetch_learnset(english_name) else {
+            return "   わざを取得できませんでした\n".to_string();
         };
+        match self
+            .version_group
+            .as_deref()
+            .or_else(|| learnset.default_version_group())
+        {
+            Some(version_group) if learnset.version_groups().contains(&version_group) => {
+                info_service.format_moves(&learnset, version_group)
+            }
+            Some(version_group) => format!(
+                "   {} で覚えるわざはありません\n",
+                version_group_ja(version_group).unwrap_or(version_group)
+            ),
+            None => "   覚えるわざがありません\n".to_string(),
+        }
+    }
 
-        Ok(result)
+    /// 表示中の系統で direction に動いた先の英名。辞書に無い種へは動かない
+    #[cfg(feature = "sprites")]
+    fn evolution_target(&self, view: &ConfirmationView, direction: Direction) -> Option<String> {
+        let (chain, position) = view.evolution.as_ref()?;
+        let next = chain.step((*position)?, direction)?;
+        self.search_service
+            .entry_by_id(chain.nodes[next].species_id)
+            .map(|entry| entry.en.clone())
+    }
+}
+
+/// 確認画面に表示中のもの。キー操作で差し替える
+#[cfg(feature = "sprites")]
+struct ConfirmationView {
+    animation: Option<SpriteAnimation>,
+    /// 進化の系統と、表示中のポケモンの位置（系統に見当たらなければ None）
+    evolution: Option<(EvolutionChain, Option<usize>)>,
+    hint: String,
+    /// 画像から案内（とその下に描き足したわざ一覧）までの行数。[v] で戻って描き直す
+    lines: u16,
+}
+
+/// 確認画面のキー操作の案内。進化の系統を辿れるときは矢印の案内も足す
+#[cfg(feature = "sprites")]
+fn navigation_hint(evolution: bool) -> String {
+    let mut hint = "   [Enter] 確定  [ESC] 再選択  [v] 画像切替  [m] わざ".to_string();
+    if evolution {
+        hint.push_str("  [←→] 進化前後  [↑↓] 分岐");
+    }
+    hint
+}
+
+/// 表示中のスプライトの種類。代わりを表示したときはそれも添える
+#[cfg(feature = "sprites")]
+fn variant_line(requested: SpriteVariant, shown: Option<SpriteVariant>) -> String {
+    match shown {
+        Some(shown) if shown != requested => format!(
+            "   表示: {}（{}の画像が無いため）",
+            shown.label(),
+            requested.label()
+        ),
+        Some(_) => format!("   表示: {}", requested.label()),
+        None => format!(
+            "   表示: {}（画像を取得できませんでした）",
+            requested.label()
+        ),
     }
 }
 
@@ -486,6 +863,59 @@ mod tests {
         assert!(!item.display(context).has_attrs());
     }
 
+    #[test]
+    fn test_with_label_shows_selected_languages() {
+        let item = PokemonItem::with_label("ピカチュウ", "Pikachu", "피카츄", "Pikachu", "");
+        assert_eq!(item.display, "피카츄 → Pikachu");
+        // 確定時の返り値は表示の言語によらず英名
+        assert_eq!(item.output(), "Pikachu");
+    }
+
+    #[test]
+    fn test_with_label_keeps_kana_searchable() {
+        let item = PokemonItem::with_label(
+            "ロコン（アローラのすがた）",
+            "Vulpix Alola",
+            "ロコン（アローラの姿）",
+            "Vulpix Alola",
+            "",
+        );
+        assert_eq!(item.display, "ロコン（アローラの姿） → Vulpix Alola");
+        assert!(item.text().contains("ロコン（アローラのすがた）"));
+        assert!(item.text().contains("rokon"));
+    }
+
+    #[test]
+    fn test_with_form_count_and_category() {
+        let item = PokemonItem::new("ミュウツー", "Mewtwo", "").with_form_count(2);
+        assert_eq!(item.display, "ミュウツー → Mewtwo [+2]");
+        // 表示は match_text の先頭のまま（ローマ字はその後ろ）
+        assert!(item.text().starts_with("ミュウツー → Mewtwo [+2] "));
+        assert_eq!(
+            PokemonItem::new("ミュウ", "Mew", "")
+                .with_form_count(0)
+                .display,
+            "ミュウ → Mew"
+        );
+
+        let item = PokemonItem::new("メガミュウツーX", "Mewtwo Mega X", "")
+            .with_form_category(Some(FormCategory::Mega));
+        let preview_context = PreviewContext {
+            query: "",
+            cmd_query: "",
+            current_index: 0,
+            current_selection: "",
+            selected_indices: &[],
+            selections: &[],
+            height: 10,
+            width: 50,
+        };
+        let ItemPreview::Text(text) = item.preview(preview_context) else {
+            panic!("Expected text preview");
+        };
+        assert!(text.ends_with("\nフォルム: メガシンカ"));
+    }
+
     #[test]
     fn test_output_returns_english_name() {
         // 確定時の返り値は表示文字列ではなく英名そのもの
@@ -549,6 +979,35 @@ mod tests {
         assert!(partial_matches.contains(&("フシギバナ", "Venusaur")));
     }
 
+    #[test]
+    fn test_select_interactive_reverse_exact_match_normalizes_case() {
+        let selector = create_test_selector();
+
+        // 完全一致なら skim を起動せず、辞書上の表記の英名を返す
+        let result = selector.select_interactive_reverse("pikachu").unwrap();
+        assert_eq!(result.as_deref(), Some("Pikachu"));
+    }
+
+    #[test]
+    fn test_select_best() {
+        let selector = create_test_selector();
+        assert_eq!(
+            selector.select_best("ピカチュウ"),
+            Some("Pikachu".to_string())
+        );
+        assert_eq!(
+            selector.select_best("ピカチュ"),
+            Some("Pikachu".to_string())
+        );
+        assert_eq!(selector.select_best("ミュウツー"), None);
+    }
+
+    #[test]
+    fn test_select_interactive_reverse_no_match() {
+        let selector = create_test_selector();
+        assert_eq!(selector.select_interactive_reverse("Mewtwo").unwrap(), None);
+    }
+
     #[test]
     fn test_select_interactive_no_match() {
         let selector = create_test_selector();
@@ -557,4 +1016,24 @@ mod tests {
         let partial_matches = selector.search_service.search_partial("ミュウツー");
         assert_eq!(partial_matches.len(), 0);
     }
+
+    #[cfg(feature = "sprites")]
+    #[test]
+    fn test_variant_line() {
+        assert_eq!(
+            variant_line(SpriteVariant::Shiny, Some(SpriteVariant::Shiny)),
+            "   表示: 色違い"
+        );
+        assert_eq!(
+            variant_line(SpriteVariant::Female, Some(SpriteVariant::Default)),
+            "   表示: 通常（メスの画像が無いため）"
+        );
+    }
+
+    #[cfg(feature = "sprites")]
+    #[test]
+    fn test_navigation_hint() {
+        assert!(!navigation_hint(false).contains('←'));
+        assert!(navigation_hint(true).ends_with("[←→] 進化前後  [↑↓] 分岐"));
+    }
 }
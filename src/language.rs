@@ -0,0 +1,82 @@
+//! 検索元・出力先の言語（--from / --to）。
+//!
+//! 日本語名（カタカナ）と英名は NameEntry の専用フィールドに、それ以外の言語は
+//! names（PokéAPI の言語コード → 名前）に入っている。どちらから引くかをここで吸収する。
+//! 日本語は漢字まじりの表記（PokéAPI の ja）も names に持つ。
+
+use clap::ValueEnum;
+
+/// 名前の言語
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Language {
+    /// 日本語（カタカナ）
+    #[default]
+    Ja,
+    /// 英語
+    En,
+    /// 韓国語
+    Ko,
+    /// 中国語（簡体字）
+    ZhHans,
+    /// 中国語（繁体字）
+    ZhHant,
+    /// フランス語
+    Fr,
+    /// ドイツ語
+    De,
+    /// スペイン語
+    Es,
+    /// イタリア語
+    It,
+}
+
+/// names に入っている漢字まじりの日本語名の言語コード
+pub const JA_KANJI_CODE: &str = "ja";
+
+/// 日本語名の表記（--ja-script）。検索はどちらの表記でも引ける
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum JaScript {
+    /// カタカナ・ひらがな（ja-Hrkt。既定）
+    #[default]
+    Kana,
+    /// 漢字まじり（ja。アローラの姿 など）。無ければカタカナ
+    Kanji,
+}
+
+impl Language {
+    /// PokéAPI の言語コード（小文字）。names のキーに使う
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Ja => "ja-hrkt",
+            Language::En => "en",
+            Language::Ko => "ko",
+            Language::ZhHans => "zh-hans",
+            Language::ZhHant => "zh-hant",
+            Language::Fr => "fr",
+            Language::De => "de",
+            Language::Es => "es",
+            Language::It => "it",
+        }
+    }
+
+    /// 引数に書く名前（ko / zh-hans など）。メッセージ用
+    pub fn arg_name(self) -> String {
+        self.to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_else(|| self.code().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_and_arg_name() {
+        assert_eq!(Language::ZhHans.code(), "zh-hans");
+        assert_eq!(Language::ZhHans.arg_name(), "zh-hans");
+        assert_eq!(Language::Ja.code(), "ja-hrkt");
+        assert_eq!(Language::Ja.arg_name(), "ja");
+        assert_eq!(Language::from_str("fr", true), Ok(Language::Fr));
+    }
+}
@@ -0,0 +1,341 @@
+//! 覚えるわざの一覧（moves サブコマンド・確認画面の [m]）。
+//!
+//! /pokemon/{id} の moves を作品（バージョングループ）ごとに持ち、選んだ作品の分を
+//! 覚え方（レベルアップ・わざマシン・タマゴわざ・おしえわざ）でまとめて表示する。
+//! わざの日本語名は呼び出し側で引いて渡す。
+
+use serde::{Deserialize, Serialize};
+
+/// 作品（バージョングループ）のスラッグと日本語名。古い順。
+/// 既定の作品を決めるときは追加コンテンツと外伝（main が false）を除いた最新を選ぶ
+const VERSION_GROUPS: &[(&str, &str, bool)] = &[
+    ("red-blue", "赤・青", true),
+    ("yellow", "ピカチュウ", true),
+    ("gold-silver", "金・銀", true),
+    ("crystal", "クリスタル", true),
+    ("ruby-sapphire", "ルビー・サファイア", true),
+    ("emerald", "エメラルド", true),
+    ("firered-leafgreen", "ファイアレッド・リーフグリーン", true),
+    ("colosseum", "コロシアム", false),
+    ("xd", "XD", false),
+    ("diamond-pearl", "ダイヤモンド・パール", true),
+    ("platinum", "プラチナ", true),
+    (
+        "heartgold-soulsilver",
+        "ハートゴールド・ソウルシルバー",
+        true,
+    ),
+    ("black-white", "ブラック・ホワイト", true),
+    ("black-2-white-2", "ブラック2・ホワイト2", true),
+    ("x-y", "X・Y", true),
+    (
+        "omega-ruby-alpha-sapphire",
+        "オメガルビー・アルファサファイア",
+        true,
+    ),
+    ("sun-moon", "サン・ムーン", true),
+    ("ultra-sun-ultra-moon", "ウルトラサン・ウルトラムーン", true),
+    (
+        "lets-go-pikachu-lets-go-eevee",
+        "Let's Go! ピカチュウ・イーブイ",
+        true,
+    ),
+    ("sword-shield", "ソード・シールド", true),
+    ("the-isle-of-armor", "鎧の孤島", false),
+    ("the-crown-tundra", "冠の雪原", false),
+    (
+        "brilliant-diamond-and-shining-pearl",
+        "ブリリアントダイヤモンド・シャイニングパール",
+        true,
+    ),
+    ("legends-arceus", "LEGENDS アルセウス", true),
+    ("scarlet-violet", "スカーレット・バイオレット", true),
+    ("the-teal-mask", "碧の仮面", false),
+    ("the-indigo-disk", "藍の円盤", false),
+];
+
+/// 作品の日本語名。知らない作品は None
+pub fn version_group_ja(slug: &str) -> Option<&'static str> {
+    VERSION_GROUPS
+        .iter()
+        .find(|(s, _, _)| *s == slug)
+        .map(|(_, ja, _)| *ja)
+}
+
+/// 古い順の並び。知らない作品は先頭扱い
+fn version_group_order(slug: &str) -> Option<usize> {
+    VERSION_GROUPS.iter().position(|(s, _, _)| *s == slug)
+}
+
+/// 覚え方。これ以外（フォルムチェンジ・特別なタマゴなど）は扱わない
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LearnMethod {
+    LevelUp,
+    Machine,
+    Egg,
+    Tutor,
+}
+
+impl LearnMethod {
+    pub const ALL: [LearnMethod; 4] = [
+        LearnMethod::LevelUp,
+        LearnMethod::Machine,
+        LearnMethod::Egg,
+        LearnMethod::Tutor,
+    ];
+
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        match slug {
+            "level-up" => Some(LearnMethod::LevelUp),
+            "machine" => Some(LearnMethod::Machine),
+            "egg" => Some(LearnMethod::Egg),
+            "tutor" => Some(LearnMethod::Tutor),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LearnMethod::LevelUp => "レベルアップ",
+            LearnMethod::Machine => "わざマシン",
+            LearnMethod::Egg => "タマゴわざ",
+            LearnMethod::Tutor => "おしえわざ",
+        }
+    }
+}
+
+/// 1作品での1つの覚え方
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LearnedMove {
+    /// わざの英語スラッグ
+    pub name: String,
+    pub version_group: String,
+    pub method: LearnMethod,
+    /// レベルアップで覚えるレベル。0 は進化したとき
+    pub level: u32,
+}
+
+/// 全作品分の覚えるわざ
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Learnset {
+    pub moves: Vec<LearnedMove>,
+}
+
+/// /pokemon/{id} の moves の1件
+#[derive(Debug, Deserialize)]
+pub struct MoveSlot {
+    #[serde(rename = "move")]
+    move_ref: Slug,
+    #[serde(default)]
+    version_group_details: Vec<VersionGroupDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionGroupDetail {
+    #[serde(default)]
+    level_learned_at: u32,
+    move_learn_method: Slug,
+    version_group: Slug,
+}
+
+#[derive(Debug, Deserialize)]
+struct Slug {
+    name: String,
+}
+
+impl Learnset {
+    /// API の moves から作る。扱わない覚え方は捨てる
+    pub fn from_slots(slots: &[MoveSlot]) -> Self {
+        let moves = slots
+            .iter()
+            .flat_map(|slot| {
+                slot.version_group_details.iter().filter_map(|detail| {
+                    Some(LearnedMove {
+                        name: slot.move_ref.name.clone(),
+                        version_group: detail.version_group.name.clone(),
+                        method: LearnMethod::from_slug(&detail.move_learn_method.name)?,
+                        level: detail.level_learned_at,
+                    })
+                })
+            })
+            .collect();
+        Self { moves }
+    }
+
+    /// わざのある作品（古い順、知らない作品は先頭）
+    pub fn version_groups(&self) -> Vec<&str> {
+        let mut groups: Vec<&str> = self
+            .moves
+            .iter()
+            .map(|m| m.version_group.as_str())
+            .collect();
+        groups.sort_by_key(|slug| (version_group_order(slug), *slug));
+        groups.dedup();
+        groups
+    }
+
+    /// 既定で表示する作品。追加コンテンツ・外伝を除いた最新。
+    /// それが無ければわざのある最新の作品
+    pub fn default_version_group(&self) -> Option<&str> {
+        let groups = self.version_groups();
+        groups
+            .iter()
+            .rev()
+            .find(|slug| {
+                VERSION_GROUPS
+                    .iter()
+                    .any(|(s, _, main)| s == *slug && *main)
+            })
+            .or(groups.last())
+            .copied()
+    }
+
+    /// 作品を絞った分
+    pub fn for_version_group<'a>(&'a self, version_group: &str) -> Vec<&'a LearnedMove> {
+        self.moves
+            .iter()
+            .filter(|m| m.version_group == version_group)
+            .collect()
+    }
+}
+
+/// 作品1つ分を覚え方ごとにまとめる。レベルアップはレベル順、ほかは名前順。
+/// `name` はわざのスラッグから表示名を引く
+pub fn format_learnset(
+    moves: &[&LearnedMove],
+    version_group: &str,
+    name: &dyn Fn(&str) -> String,
+) -> String {
+    let title = version_group_ja(version_group).unwrap_or(version_group);
+    let mut out = format!("わざ（{}）\n", title);
+    if moves.is_empty() {
+        out.push_str("  覚えるわざがありません\n");
+        return out;
+    }
+
+    for method in LearnMethod::ALL {
+        let mut rows: Vec<(u32, String)> = moves
+            .iter()
+            .filter(|m| m.method == method)
+            .map(|m| (m.level, name(&m.name)))
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+        if method == LearnMethod::LevelUp {
+            rows.sort();
+        } else {
+            rows.sort_by(|a, b| a.1.cmp(&b.1));
+        }
+        rows.dedup();
+
+        out.push_str(&format!("{}\n", method.label()));
+        for (level, move_name) in rows {
+            match method {
+                LearnMethod::LevelUp if level == 0 => {
+                    out.push_str(&format!("  進化    {}\n", move_name));
+                }
+                LearnMethod::LevelUp => {
+                    out.push_str(&format!("  Lv.{:>3}  {}\n", level, move_name));
+                }
+                _ => out.push_str(&format!("  {}\n", move_name)),
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn learned(name: &str, version_group: &str, method: LearnMethod, level: u32) -> LearnedMove {
+        LearnedMove {
+            name: name.to_string(),
+            version_group: version_group.to_string(),
+            method,
+            level,
+        }
+    }
+
+    #[test]
+    fn test_from_slots_skips_unknown_methods() {
+        let slots: Vec<MoveSlot> = serde_json::from_str(
+            r#"[
+                {"move":{"name":"thunder-shock"},"version_group_details":[
+                    {"level_learned_at":1,"move_learn_method":{"name":"level-up"},"version_group":{"name":"x-y"}},
+                    {"level_learned_at":0,"move_learn_method":{"name":"stadium-surfing-pikachu"},"version_group":{"name":"x-y"}}
+                ]},
+                {"move":{"name":"thunderbolt"},"version_group_details":[
+                    {"level_learned_at":0,"move_learn_method":{"name":"machine"},"version_group":{"name":"sun-moon"}}
+                ]}
+            ]"#,
+        )
+        .unwrap();
+        let learnset = Learnset::from_slots(&slots);
+        assert_eq!(
+            learnset.moves,
+            [
+                learned("thunder-shock", "x-y", LearnMethod::LevelUp, 1),
+                learned("thunderbolt", "sun-moon", LearnMethod::Machine, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_version_groups_and_default() {
+        let learnset = Learnset {
+            moves: vec![
+                learned("a", "the-indigo-disk", LearnMethod::Tutor, 0),
+                learned("a", "x-y", LearnMethod::Machine, 0),
+                learned("b", "scarlet-violet", LearnMethod::Machine, 0),
+                learned("c", "x-y", LearnMethod::Egg, 0),
+            ],
+        };
+        assert_eq!(
+            learnset.version_groups(),
+            ["x-y", "scarlet-violet", "the-indigo-disk"]
+        );
+        // 追加コンテンツより本編を選ぶ
+        assert_eq!(learnset.default_version_group(), Some("scarlet-violet"));
+        assert_eq!(learnset.for_version_group("x-y").len(), 2);
+
+        let dlc_only = Learnset {
+            moves: vec![learned("a", "the-teal-mask", LearnMethod::Tutor, 0)],
+        };
+        assert_eq!(dlc_only.default_version_group(), Some("the-teal-mask"));
+        assert_eq!(Learnset::default().default_version_group(), None);
+    }
+
+    #[test]
+    fn test_format_learnset() {
+        let moves = [
+            learned("thunderbolt", "x-y", LearnMethod::Machine, 0),
+            learned("quick-attack", "x-y", LearnMethod::LevelUp, 10),
+            learned("thunder-shock", "x-y", LearnMethod::LevelUp, 1),
+            learned("volt-tackle", "x-y", LearnMethod::Egg, 0),
+        ];
+        let refs: Vec<&LearnedMove> = moves.iter().collect();
+        let name = |slug: &str| match slug {
+            "thunderbolt" => "10まんボルト".to_string(),
+            "quick-attack" => "でんこうせっか".to_string(),
+            "thunder-shock" => "でんきショック".to_string(),
+            other => other.to_string(),
+        };
+        assert_eq!(
+            format_learnset(&refs, "x-y", &name),
+            "わざ（X・Y）\n\
+             レベルアップ\n\
+             \x20 Lv.  1  でんきショック\n\
+             \x20 Lv. 10  でんこうせっか\n\
+             わざマシン\n\
+             \x20 10まんボルト\n\
+             タマゴわざ\n\
+             \x20 volt-tackle\n"
+        );
+        assert_eq!(
+            format_learnset(&[], "unknown-game", &name),
+            "わざ（unknown-game）\n  覚えるわざがありません\n"
+        );
+    }
+}
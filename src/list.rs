@@ -0,0 +1,288 @@
+//! 辞書の一覧表示（list サブコマンド）。
+//!
+//! タイプ・図鑑番号・世代・種かフォルムかで絞り込んで、辞書の一部を書き出す。
+//! names.json だけで完結し、ネットワークには触れない。並びは図鑑順
+//! （フォルムは元の種の直後）。
+
+use crate::models::NameEntry;
+use crate::search::SearchService;
+use clap::ValueEnum;
+
+/// 各世代の最後の図鑑番号（第1世代 151 〜 第9世代 1025）
+const GENERATION_LAST_IDS: [u32; 9] = [151, 251, 386, 493, 649, 721, 809, 905, 1025];
+
+/// 種とフォルムのどちらを出すか
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ListKind {
+    /// 両方（既定）
+    #[default]
+    All,
+    /// 種のみ
+    Species,
+    /// フォルムのみ（メガシンカ・リージョンフォーム等）
+    Forms,
+}
+
+/// 図鑑番号の範囲（両端を含む）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl IdRange {
+    pub fn contains(&self, id: u32) -> bool {
+        (self.min..=self.max).contains(&id)
+    }
+}
+
+/// 一覧の絞り込み条件。指定したものはすべて満たす必要がある（AND）
+#[derive(Debug, Clone, Default)]
+pub struct ListFilter {
+    /// タイプの英語スラッグ。すべてを持つエントリだけ残す
+    pub types: Vec<String>,
+    /// エントリ自身の id（フォルムは 10001 以降）の範囲
+    pub ids: Option<IdRange>,
+    /// 世代（1〜9）。フォルムは元の種の世代で判定する
+    pub generation: Option<u8>,
+    pub kind: ListKind,
+}
+
+impl ListFilter {
+    /// `species_id` はフォルムなら元の種の図鑑番号（SearchService::species_id）
+    pub fn matches(&self, entry: &NameEntry, species_id: Option<u32>) -> bool {
+        let is_form = entry.is_form();
+        let kind_ok = match self.kind {
+            ListKind::All => true,
+            ListKind::Species => !is_form,
+            ListKind::Forms => is_form,
+        };
+
+        kind_ok
+            && self.types.iter().all(|t| entry.types.contains(t))
+            && self
+                .ids
+                .is_none_or(|range| entry.id.is_some_and(|id| range.contains(id)))
+            && self
+                .generation
+                .is_none_or(|g| species_id.and_then(generation_of) == Some(g))
+    }
+}
+
+/// 条件に合うエントリを図鑑順で返す
+pub fn list_entries<'a>(service: &'a SearchService, filter: &ListFilter) -> Vec<&'a NameEntry> {
+    service
+        .all_entries()
+        .into_iter()
+        .filter_map(|(ja, _)| service.entry(ja))
+        .filter(|entry| filter.matches(entry, service.species_id(&entry.ja)))
+        .collect()
+}
+
+/// 図鑑番号から世代を求める。第9世代より後の番号も第9世代に含める
+pub fn generation_of(species_id: u32) -> Option<u8> {
+    if species_id == 0 {
+        return None;
+    }
+    let index = GENERATION_LAST_IDS
+        .iter()
+        .position(|last| species_id <= *last)
+        .unwrap_or(GENERATION_LAST_IDS.len() - 1);
+    u8::try_from(index + 1).ok()
+}
+
+/// `--id` の値を読む。`25`、`1-151`、`1-`（以降すべて）、`-151`（以前すべて）
+pub fn parse_id_range(value: &str) -> Result<IdRange, String> {
+    let parse = |s: &str, default: u32| -> Result<u32, String> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(default);
+        }
+        s.parse()
+            .map_err(|_| format!("図鑑番号として読めません: {}", s))
+    };
+
+    let range = match value.split_once('-') {
+        Some((min, max)) => IdRange {
+            min: parse(min, 0)?,
+            max: parse(max, u32::MAX)?,
+        },
+        None => {
+            let id = parse(value, 0)?;
+            IdRange { min: id, max: id }
+        }
+    };
+
+    if range.min > range.max {
+        return Err(format!("範囲の始まりが終わりより大きいです: {}", value));
+    }
+    Ok(range)
+}
+
+/// `--type` の値を読む。日本語名（ほのお）でも英語スラッグ（fire）でもよい
+pub fn parse_type(value: &str) -> Result<String, String> {
+    crate::pokemon_type::type_slug(value)
+        .map(str::to_string)
+        .ok_or_else(|| format!("不明なタイプです: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NameDictionary;
+    use chrono::Utc;
+
+    fn entry(ja: &str, en: &str, id: u32, types: &[&str]) -> NameEntry {
+        NameEntry {
+            ja: ja.to_string(),
+            en: en.to_string(),
+            id: Some(id),
+            types: types.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn create_service() -> SearchService {
+        let entries = vec![
+            entry("リザードン", "Charizard", 6, &["fire", "flying"]),
+            entry(
+                "メガリザードンY",
+                "Mega Charizard Y",
+                10035,
+                &["fire", "flying"],
+            ),
+            entry(
+                "メガリザードンX",
+                "Mega Charizard X",
+                10034,
+                &["fire", "dragon"],
+            ),
+            entry("ホウオウ", "Ho-Oh", 250, &["fire", "flying"]),
+            entry("ピカチュウ", "Pikachu", 25, &["electric"]),
+            entry("ロコン", "Vulpix", 37, &["fire"]),
+            entry(
+                "ロコン（アローラのすがた）",
+                "Alolan Vulpix",
+                10103,
+                &["ice"],
+            ),
+        ];
+        SearchService::from_dictionary(&NameDictionary {
+            schema_version: 2,
+            generated_at: Utc::now(),
+            count: entries.len(),
+            entries,
+        })
+    }
+
+    fn names(service: &SearchService, filter: &ListFilter) -> Vec<String> {
+        list_entries(service, filter)
+            .into_iter()
+            .map(|e| e.ja.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_list_all_in_dex_order() {
+        let service = create_service();
+        assert_eq!(
+            names(&service, &ListFilter::default()),
+            [
+                "リザードン",
+                "メガリザードンX",
+                "メガリザードンY",
+                "ピカチュウ",
+                "ロコン",
+                "ロコン（アローラのすがた）",
+                "ホウオウ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_by_types_requires_all() {
+        let service = create_service();
+        let filter = ListFilter {
+            types: vec!["fire".to_string(), "flying".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            names(&service, &filter),
+            ["リザードン", "メガリザードンY", "ホウオウ"]
+        );
+    }
+
+    #[test]
+    fn test_list_by_id_range_and_kind() {
+        let service = create_service();
+        let filter = ListFilter {
+            ids: Some(IdRange { min: 1, max: 151 }),
+            types: vec!["fire".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(names(&service, &filter), ["リザードン", "ロコン"]);
+
+        let filter = ListFilter {
+            kind: ListKind::Forms,
+            ..Default::default()
+        };
+        assert_eq!(
+            names(&service, &filter),
+            [
+                "メガリザードンX",
+                "メガリザードンY",
+                "ロコン（アローラのすがた）"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_by_generation_includes_forms_of_that_generation() {
+        let service = create_service();
+        let filter = ListFilter {
+            generation: Some(1),
+            types: vec!["ice".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(names(&service, &filter), ["ロコン（アローラのすがた）"]);
+
+        let filter = ListFilter {
+            generation: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(names(&service, &filter), ["ホウオウ"]);
+    }
+
+    #[test]
+    fn test_generation_of() {
+        assert_eq!(generation_of(1), Some(1));
+        assert_eq!(generation_of(151), Some(1));
+        assert_eq!(generation_of(152), Some(2));
+        assert_eq!(generation_of(1025), Some(9));
+        assert_eq!(generation_of(1100), Some(9));
+        assert_eq!(generation_of(0), None);
+    }
+
+    #[test]
+    fn test_parse_id_range() {
+        assert_eq!(parse_id_range("25"), Ok(IdRange { min: 25, max: 25 }));
+        assert_eq!(parse_id_range("1-151"), Ok(IdRange { min: 1, max: 151 }));
+        assert_eq!(
+            parse_id_range("10001-"),
+            Ok(IdRange {
+                min: 10001,
+                max: u32::MAX
+            })
+        );
+        assert_eq!(parse_id_range("-151"), Ok(IdRange { min: 0, max: 151 }));
+        assert!(parse_id_range("151-1").is_err());
+        assert!(parse_id_range("abc").is_err());
+    }
+
+    #[test]
+    fn test_parse_type() {
+        assert_eq!(parse_type("ほのお"), Ok("fire".to_string()));
+        assert_eq!(parse_type("Fire"), Ok("fire".to_string()));
+        assert!(parse_type("ひかり").is_err());
+    }
+}
@@ -1,21 +1,44 @@
+mod batch;
+mod cache;
+#[cfg(feature = "sprites")]
+mod compare;
+mod crawler;
 #[cfg(feature = "cries")]
 mod cry;
 mod data;
 #[cfg(feature = "sprites")]
+mod evolution;
+#[cfg(feature = "sprites")]
 mod info;
 mod interactive;
+mod language;
+#[cfg(feature = "sprites")]
+mod learnset;
+mod list;
 mod models;
+mod output;
 mod pokemon_type;
+mod prefetch;
+#[cfg(feature = "sprites")]
+mod render;
 mod romaji;
 mod search;
 #[cfg(feature = "sprites")]
 mod sprite;
+// 今は情報表示（sprites）からしか使わない
+#[cfg(feature = "sprites")]
+mod type_chart;
 mod update;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use cache::CacheKind;
 use clap::{Parser, Subcommand};
 use interactive::InteractiveSelector;
-use search::SearchService;
+use language::{JaScript, Language};
+use list::{IdRange, ListFilter, ListKind};
+use models::NameEntry;
+use output::{NameStyle, OutputFormat, OutputName};
+use search::{FormFilter, SearchService};
 use std::path::PathBuf;
 use std::process;
 use update::UpdateService;
@@ -28,8 +51,8 @@ use update::UpdateService;
     long_about = "日本語名（カタカナ）を入力すると PokéAPI 準拠の英名を返すCLI。\n出力結果は Pokemiro でそのまま利用できます。"
 )]
 struct Cli {
-    /// ポケモンの日本語名（カタカナ、種レベル）
-    #[arg(help = "ポケモンの日本語名（カタカナ、種レベル）")]
+    /// ポケモンの日本語名（カタカナ、種レベル。ローマ字も可）
+    #[arg(help = "ポケモンの日本語名（カタカナ、種レベル。ローマ字も可）")]
     japanese_name: Option<String>,
 
     /// names.json の明示パス
@@ -40,10 +63,156 @@ struct Cli {
     #[arg(long = "show-sprite", short = 's', help = "スプライト画像を表示")]
     show_sprite: bool,
 
+    /// 表示するスプライトの種類（無い場合は近いものに代える）
+    #[cfg(feature = "sprites")]
+    #[arg(
+        long,
+        value_enum,
+        value_name = "VARIANT",
+        default_value = "default",
+        help = "表示するスプライトの種類（無い場合は近いものに代える）"
+    )]
+    sprite_variant: sprite::SpriteVariant,
+
+    /// 動くスプライト（GIF）があればキーを押すまで再生する
+    #[cfg(feature = "sprites")]
+    #[arg(
+        long,
+        requires = "show_sprite",
+        help = "動くスプライト（GIF）があればキーを押すまで再生する"
+    )]
+    animate: bool,
+
+    /// 静止画の描き方。auto は Kitty / iTerm2 なら画像、それ以外はブロック
+    #[cfg(feature = "sprites")]
+    #[arg(
+        long,
+        value_enum,
+        value_name = "RENDERER",
+        default_value = "auto",
+        help = "静止画の描き方。auto は Kitty / iTerm2 なら画像、それ以外はブロック"
+    )]
+    sprite_renderer: sprite::SpriteRenderer,
+
+    /// スプライトの表示幅（文字数）。高さだけ・幅だけなら縦横比を保つ
+    #[cfg(feature = "sprites")]
+    #[arg(
+        long,
+        value_name = "COLUMNS",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "スプライトの表示幅（文字数）。高さだけ・幅だけなら縦横比を保つ"
+    )]
+    sprite_width: Option<u32>,
+
+    /// スプライトの表示高さ（行数）
+    #[cfg(feature = "sprites")]
+    #[arg(
+        long,
+        value_name = "ROWS",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "スプライトの表示高さ（行数）"
+    )]
+    sprite_height: Option<u32>,
+
+    /// わざ一覧で表示する作品（scarlet-violet など。既定は最新作）
+    #[cfg(feature = "sprites")]
+    #[arg(
+        long,
+        value_name = "VERSION_GROUP",
+        help = "わざ一覧で表示する作品（scarlet-violet など。既定は最新作）"
+    )]
+    version_group: Option<String>,
+
     /// 鳴き声を再生
     #[arg(long = "play-cry", short = 'c', help = "鳴き声を再生")]
     play_cry: bool,
 
+    /// 英名から日本語名を逆引き（--from en と同じ）
+    #[arg(
+        long,
+        short = 'r',
+        help = "英名から日本語名を逆引き（--from en と同じ）"
+    )]
+    reverse: bool,
+
+    /// 入力する名前の言語（既定は日本語）
+    #[arg(
+        long,
+        value_enum,
+        value_name = "LANG",
+        conflicts_with = "reverse",
+        help = "入力する名前の言語（既定は日本語）"
+    )]
+    from: Option<Language>,
+
+    /// 出力する名前の言語（既定は英語。--from en なら日本語）
+    #[arg(
+        long,
+        value_enum,
+        value_name = "LANG",
+        help = "出力する名前の言語（既定は英語。--from en なら日本語）"
+    )]
+    to: Option<Language>,
+
+    /// 対話選択の一覧に出す日本語名の表記（検索はどちらでも引ける）
+    #[arg(
+        long,
+        value_enum,
+        value_name = "SCRIPT",
+        default_value_t = JaScript::Kana,
+        help = "対話選択の一覧に出す日本語名の表記（検索はどちらでも引ける）"
+    )]
+    ja_script: JaScript,
+
+    /// フォルムの扱い（collapse は種にまとめ、species は種だけを引く）
+    #[arg(
+        long,
+        value_enum,
+        value_name = "MODE",
+        default_value_t = FormFilter::All,
+        help = "フォルムの扱い（collapse は種にまとめ、species は種だけを引く）"
+    )]
+    forms: FormFilter,
+
+    /// ファイル（省略時や - は標準入力）から1行1件でまとめて変換
+    #[arg(
+        long,
+        short = 'b',
+        value_name = "FILE",
+        num_args = 0..=1,
+        default_missing_value = "-",
+        conflicts_with = "japanese_name",
+        help = "ファイル（省略時や - は標準入力）から1行1件でまとめて変換。\
+                1行でも見つからなければ終了コード 2（件数は標準エラーの最後の行）"
+    )]
+    batch: Option<PathBuf>,
+
+    /// 対話選択を開かず、綴り違いも含めて最も近い1件を採用
+    #[arg(
+        long,
+        conflicts_with = "reverse",
+        help = "対話選択を開かず、綴り違いも含めて最も近い1件を採用"
+    )]
+    best: bool,
+
+    /// 出力形式（json / tsv / csv は ja・en・id・types を含む）
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        help = "出力形式（json / tsv / csv は ja・en・id・types を含む）"
+    )]
+    format: OutputFormat,
+
+    /// text で名前の代わりに出すもの（en は --to によらず英名）
+    #[arg(
+        long,
+        value_enum,
+        value_name = "NAME",
+        help = "text で名前の代わりに出すもの（en は --to によらず英名）"
+    )]
+    output: Option<OutputName>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -56,15 +225,20 @@ enum Commands {
         #[arg(long, help = "PokéAPI を直接クロールして生成（通常は不要）")]
         online: bool,
 
-        /// CI配布のURLを上書き
-        #[arg(long = "source", value_name = "URL", help = "CI配布のURLを上書き")]
+        /// CI配布のURLを上書き（--online 時は PokéAPI のベースURL）
+        #[arg(
+            long = "source",
+            value_name = "URL",
+            help = "CI配布のURLを上書き（--online 時は PokéAPI のベースURL）"
+        )]
         source_url: Option<String>,
 
         /// 取得ファイルの検証
         #[arg(
             long = "verify-sha256",
             value_name = "HEX",
-            help = "取得ファイルの検証"
+            help = "取得ファイルの検証",
+            conflicts_with = "online"
         )]
         verify_sha256: Option<String>,
 
@@ -72,6 +246,149 @@ enum Commands {
         #[arg(long, help = "置換せず検証のみ")]
         dry_run: bool,
     },
+
+    /// 辞書の一覧を図鑑順で表示（タイプ・番号・世代で絞り込み）
+    List {
+        /// タイプで絞り込み（ほのお / fire）。複数指定はすべてを持つもの
+        #[arg(
+            long = "type",
+            short = 't',
+            value_name = "TYPE",
+            value_parser = list::parse_type,
+            help = "タイプで絞り込み（ほのお / fire）。複数指定はすべてを持つもの"
+        )]
+        types: Vec<String>,
+
+        /// id の範囲（25 / 1-151 / 10001-）。フォルムは 10001 以降
+        #[arg(
+            long = "id",
+            value_name = "RANGE",
+            value_parser = list::parse_id_range,
+            help = "id の範囲（25 / 1-151 / 10001-）。フォルムは 10001 以降"
+        )]
+        ids: Option<IdRange>,
+
+        /// 世代で絞り込み（フォルムは元のポケモンの世代）
+        #[arg(
+            long,
+            short = 'g',
+            value_name = "N",
+            value_parser = clap::value_parser!(u8).range(1..=9),
+            help = "世代で絞り込み（フォルムは元のポケモンの世代）"
+        )]
+        generation: Option<u8>,
+
+        /// 種とフォルムのどちらを出すか
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = ListKind::All,
+            help = "種とフォルムのどちらを出すか"
+        )]
+        kind: ListKind,
+
+        /// 出力形式。`list` の前に書いた --format と同じ（後ろに書いたほうが優先）
+        #[arg(
+            long,
+            value_enum,
+            help = "出力形式（json / tsv / csv は ja・en・id・types を含む）"
+        )]
+        format: Option<OutputFormat>,
+
+        /// text で名前の代わりに出すもの。`list` の前に書いた --output と同じ
+        #[arg(
+            long,
+            value_enum,
+            value_name = "NAME",
+            help = "text で名前の代わりに出すもの（en は --to によらず英名）"
+        )]
+        output: Option<OutputName>,
+    },
+
+    /// 全ポケモンのスプライト・鳴き声・情報を事前に取得（オフライン用）
+    Prefetch {
+        /// 取得する種類（既定はすべて）
+        #[arg(
+            long,
+            value_enum,
+            value_name = "KIND",
+            help = "取得する種類（既定はすべて）"
+        )]
+        only: Vec<CacheKind>,
+
+        /// 同時に取得する数
+        #[arg(
+            long,
+            short = 'j',
+            value_name = "N",
+            default_value_t = prefetch::DEFAULT_JOBS,
+            value_parser = clap::value_parser!(usize),
+            help = "同時に取得する数"
+        )]
+        jobs: usize,
+    },
+
+    /// スプライト・鳴き声・情報のキャッシュを管理
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// 複数のポケモンのスプライト・タイプ・種族値を横に並べて比べる
+    #[cfg(feature = "sprites")]
+    Compare {
+        /// 比べるポケモン（日本語名・ローマ字・英名。1つ目が差分の基準）
+        #[arg(required = true, num_args = 2.., value_name = "NAME")]
+        names: Vec<String>,
+    },
+
+    /// 覚えるわざを覚え方（レベル・わざマシン・タマゴ・おしえわざ）ごとに表示
+    #[cfg(feature = "sprites")]
+    Moves {
+        /// ポケモン（日本語名・ローマ字・英名）
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// 種類ごとの件数と容量を表示
+    Stats,
+
+    /// 壊れたファイル（読み込めない画像・音声・情報）を探す
+    Verify {
+        /// 対象の種類（既定はすべて）
+        #[arg(
+            long,
+            value_enum,
+            value_name = "KIND",
+            help = "対象の種類（既定はすべて）"
+        )]
+        only: Vec<CacheKind>,
+
+        /// 見つかったファイルを削除（次回表示時に取り直す）
+        #[arg(long, help = "見つかったファイルを削除（次回表示時に取り直す）")]
+        delete: bool,
+    },
+
+    /// 辞書に無いポケモンのファイルと、取得途中の .part を削除
+    Prune {
+        /// 削除せず対象だけ表示
+        #[arg(long, help = "削除せず対象だけ表示")]
+        dry_run: bool,
+    },
+
+    /// 指定した種類のキャッシュを削除
+    Clear {
+        /// 削除する種類
+        #[arg(value_enum, required_unless_present = "all", help = "削除する種類")]
+        kinds: Vec<CacheKind>,
+
+        /// すべての種類を削除
+        #[arg(long, conflicts_with = "kinds", help = "すべての種類を削除")]
+        all: bool,
+    },
 }
 
 fn main() {
@@ -88,6 +405,18 @@ fn main() {
 fn run() -> Result<i32> {
     let cli = Cli::parse();
 
+    // -r は --from en の短縮。出力先は指定が無ければ検索元でない方（日本語か英語）
+    let from = if cli.reverse {
+        Language::En
+    } else {
+        cli.from.unwrap_or_default()
+    };
+    let to = cli.to.unwrap_or(if from == Language::En {
+        Language::Ja
+    } else {
+        Language::En
+    });
+
     match cli.command {
         Some(Commands::Update {
             online,
@@ -95,47 +424,222 @@ fn run() -> Result<i32> {
             verify_sha256,
             dry_run,
         }) => handle_update(cli.dict_path, online, source_url, verify_sha256, dry_run),
+        Some(Commands::List {
+            types,
+            ids,
+            generation,
+            kind,
+            format,
+            output,
+        }) => {
+            let filter = ListFilter {
+                types,
+                ids,
+                generation,
+                kind,
+            };
+            handle_list(
+                cli.dict_path,
+                &filter,
+                format.unwrap_or(cli.format),
+                NameStyle::new(to, output.or(cli.output)),
+            )
+        }
+        Some(Commands::Prefetch { only, jobs }) => handle_prefetch(cli.dict_path, &only, jobs),
+        Some(Commands::Cache { action }) => handle_cache(cli.dict_path, action),
+        #[cfg(feature = "sprites")]
+        Some(Commands::Compare { names }) => handle_compare(
+            cli.dict_path,
+            &names,
+            from,
+            to,
+            cli.sprite_variant,
+            cli.sprite_width,
+            cli.sprite_height,
+        ),
+        #[cfg(feature = "sprites")]
+        Some(Commands::Moves { name }) => {
+            handle_moves(cli.dict_path, &name, from, to, cli.version_group.as_deref())
+        }
         None => {
+            let options = SearchOptions {
+                show_sprite: cli.show_sprite,
+                #[cfg(feature = "sprites")]
+                sprite_variant: cli.sprite_variant,
+                #[cfg(feature = "sprites")]
+                animate: cli.animate,
+                #[cfg(feature = "sprites")]
+                sprite_renderer: cli.sprite_renderer,
+                #[cfg(feature = "sprites")]
+                sprite_width: cli.sprite_width,
+                #[cfg(feature = "sprites")]
+                sprite_height: cli.sprite_height,
+                #[cfg(feature = "sprites")]
+                version_group: cli.version_group,
+                play_cry: cli.play_cry,
+                from,
+                to,
+                output: cli.output,
+                ja_script: cli.ja_script,
+                forms: cli.forms,
+                best: cli.best,
+                format: cli.format,
+            };
+
             // 検索機能
-            if let Some(japanese_name) = cli.japanese_name {
-                search_pokemon(&japanese_name, cli.dict_path, cli.show_sprite, cli.play_cry)
+            if let Some(input) = cli.batch {
+                search_batch(&input, cli.dict_path, &options)
+            } else if let Some(name) = cli.japanese_name {
+                search_pokemon(&name, cli.dict_path, &options)
             } else {
-                // 引数なしの場合、全候補からインタラクティブ選択
-                search_interactive_all(cli.dict_path, cli.show_sprite, cli.play_cry)
+                // 引数なしの場合、全候補からインタラクティブ選択。
+                // -r / --from だけなら、その言語の名前で絞り込む一覧になる
+                search_interactive_all(cli.dict_path, &options)
             }
         }
     }
 }
 
-fn search_pokemon(
-    japanese_name: &str,
-    dict_path: Option<PathBuf>,
-    #[allow(unused_variables)] show_sprite: bool,
+/// 検索系の共通オプション
+struct SearchOptions {
+    show_sprite: bool,
+    #[cfg(feature = "sprites")]
+    sprite_variant: sprite::SpriteVariant,
+    #[cfg(feature = "sprites")]
+    animate: bool,
+    #[cfg(feature = "sprites")]
+    sprite_renderer: sprite::SpriteRenderer,
+    #[cfg(feature = "sprites")]
+    sprite_width: Option<u32>,
+    #[cfg(feature = "sprites")]
+    sprite_height: Option<u32>,
+    #[cfg(feature = "sprites")]
+    version_group: Option<String>,
     play_cry: bool,
-) -> Result<i32> {
-    // SearchServiceを初期化
+    /// 入力する名前の言語。英語なら逆引き
+    from: Language,
+    /// 出力する名前の言語
+    to: Language,
+    /// 名前の代わりに出すもの（スラッグ・Showdown の ID など）
+    output: Option<OutputName>,
+    /// 対話選択の一覧と「もしかして」に出す日本語名の表記
+    ja_script: JaScript,
+    /// フォルムを種にまとめるか、種だけにするか
+    forms: FormFilter,
+    /// 対話選択せず、綴り違いの最上位候補まで自動で採る
+    best: bool,
+    format: OutputFormat,
+}
+
+impl SearchOptions {
+    /// 英名からの逆引きか
+    fn reverse(&self) -> bool {
+        self.from == Language::En
+    }
+
+    fn style(&self) -> NameStyle {
+        NameStyle::new(self.to, self.output)
+    }
+}
+
+/// 「もしかして」に並べる候補数
+const SUGGESTION_LIMIT: usize = 5;
+
+/// 辞書を読み込み、検索元の言語で索引を作る。
+/// v2 以前の辞書は日本語と英語しか持たないので、ほかの言語なら更新を促して止める。
+/// スラッグを持たない辞書で --output slug / showdown、id を持たない辞書で --output id のときも同じ
+fn load_search_service(
+    dict_path: Option<PathBuf>,
+    from: Language,
+    style: NameStyle,
+) -> Result<SearchService> {
     let search_service = if let Some(path) = dict_path {
         SearchService::with_path(path)?
     } else {
         SearchService::new()?
     };
+    for language in [from, style.to] {
+        if !search_service.has_language(language) {
+            return Err(anyhow::anyhow!(
+                "Dictionary has no '{}' names. Run 'poke-lookup update' to download the latest data file.",
+                language.arg_name()
+            ));
+        }
+    }
+    if style.needs_slug() && !search_service.has_slugs() {
+        return Err(anyhow::anyhow!(
+            "Dictionary has no slugs. Run 'poke-lookup update' to download the latest data file."
+        ));
+    }
+    if style.needs_id() && !search_service.has_ids() {
+        return Err(anyhow::anyhow!(
+            "Dictionary has no ids. Run 'poke-lookup update' to download the latest data file."
+        ));
+    }
+    Ok(search_service.source_language(from))
+}
+
+/// --dict があればその辞書、無ければデータディレクトリの names.json を読むローダー
+fn data_loader(dict_path: Option<PathBuf>) -> Result<data::DataLoader> {
+    match dict_path {
+        Some(path) => Ok(data::DataLoader::with_path(path)),
+        None => data::DataLoader::new(),
+    }
+}
+
+/// 1件分の結果を標準出力へ。表形式ならヘッダーも添える。
+/// text 形式で見つからなかったときは何も出さない（メッセージは標準エラーへ）
+fn print_record(format: OutputFormat, query: &str, entry: Option<&NameEntry>, style: NameStyle) {
+    if let Some(record) = format.record(query, entry, style) {
+        if let Some(header) = format.header() {
+            println!("{}", header);
+        }
+        println!("{}", record);
+    }
+}
+
+fn search_pokemon(query: &str, dict_path: Option<PathBuf>, options: &SearchOptions) -> Result<i32> {
+    // SearchServiceを初期化
+    let loader = data_loader(dict_path.clone())?;
+    let search_service = load_search_service(dict_path, options.from, options.style())?
+        .ja_script(options.ja_script)
+        .form_filter(options.forms);
 
     // インタラクティブセレクターを作成
     let selector = InteractiveSelector::new(search_service.clone())
-        .show_sprite(show_sprite)
-        .play_cry(play_cry);
+        .languages(options.from, options.to)
+        .show_sprite(options.show_sprite, &loader)
+        .play_cry(options.play_cry, &loader);
+    #[cfg(feature = "sprites")]
+    let selector = selector
+        .sprite_variant(options.sprite_variant)
+        .animate(options.animate)
+        .sprite_size(options.sprite_width, options.sprite_height)
+        .sprite_renderer(options.sprite_renderer)
+        .version_group(options.version_group.clone());
 
-    // 検索実行
-    match selector.select_interactive(japanese_name)? {
-        Some(english_name) => {
-            // 成功: 英名を標準出力
-            println!("{}", english_name);
+    // 検索実行。逆引きでも確定するのは英名なので、エントリに引き直して出力する
+    let selected = if options.reverse() {
+        selector.select_interactive_reverse(query)?
+    } else if options.best {
+        selector.select_best(query)
+    } else {
+        selector.select_interactive(query)?
+    };
+
+    match selected
+        .as_deref()
+        .and_then(|en| search_service.entry_by_en(en))
+    {
+        Some(entry) => {
+            // 成功: 結果を標準出力
+            print_record(options.format, query, Some(entry), options.style());
 
             // スプライト表示
             #[cfg(feature = "sprites")]
             {
-                if show_sprite {
-                    display_sprite_for_pokemon(&english_name, &search_service)?;
+                if options.show_sprite {
+                    display_sprite_for_pokemon(&entry.en, &loader, options)?;
                 }
             }
 
@@ -146,40 +650,118 @@ fn search_pokemon(
         }
         None => {
             // 候補なし
-            eprintln!("候補が見つかりませんでした: {}", japanese_name);
+            eprintln!("候補が見つかりませんでした: {}", query);
+            // 部分一致の候補があったのに選ばなかった（キャンセル）場合は出さない
+            if !options.reverse()
+                && (options.best || search_service.search_partial(query).is_empty())
+            {
+                print_suggestions(&search_service, query, options.from, options.to);
+            }
+            print_record(options.format, query, None, options.style());
             Ok(2)
         }
     }
 }
 
-fn search_interactive_all(
+/// 綴り違いの候補を類似度順に標準エラーへ。検索元 → 出力先の言語の名前で並べる
+fn print_suggestions(search_service: &SearchService, query: &str, from: Language, to: Language) {
+    let suggestions = search_service.suggest(query, SUGGESTION_LIMIT);
+    if suggestions.is_empty() {
+        return;
+    }
+    eprintln!("もしかして:");
+    for suggestion in suggestions {
+        eprintln!(
+            "  {} → {}",
+            search_service
+                .name_in(suggestion.ja, from)
+                .unwrap_or(suggestion.ja),
+            search_service
+                .name_in(suggestion.ja, to)
+                .unwrap_or(suggestion.en)
+        );
+    }
+}
+
+fn search_batch(
+    input: &std::path::Path,
     dict_path: Option<PathBuf>,
-    #[allow(unused_variables)] show_sprite: bool,
-    play_cry: bool,
+    options: &SearchOptions,
 ) -> Result<i32> {
-    // SearchServiceを初期化
-    let search_service = if let Some(path) = dict_path {
-        SearchService::with_path(path)?
+    use std::fs::File;
+    use std::io::{self, BufRead, BufReader};
+
+    // SearchServiceを初期化（全行で使い回す）
+    let search_service =
+        load_search_service(dict_path, options.from, options.style())?.form_filter(options.forms);
+
+    let reader: Box<dyn BufRead> = if input.as_os_str() == "-" {
+        Box::new(io::stdin().lock())
     } else {
-        SearchService::new()?
+        let file = File::open(input)
+            .with_context(|| format!("Failed to open input file: {}", input.display()))?;
+        Box::new(BufReader::new(file))
     };
 
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let report =
+        batch::translate_lines(reader, &mut out, options.format, options.style(), |name| {
+            if options.reverse() {
+                search_service.entry_by_en(name)
+            } else if options.best {
+                search_service.best_match(name)
+            } else {
+                search_service.entry_exact(name)
+            }
+        })?;
+
+    // 失敗行は行番号付きで標準エラーへ。標準出力は結果だけに保つ
+    for failure in &report.failures {
+        eprintln!(
+            "{}: 候補が見つかりませんでした: {}",
+            failure.line_no, failure.input
+        );
+    }
+    if let Some(summary) = report.summary() {
+        eprintln!("{}", summary);
+    }
+
+    Ok(report.exit_code())
+}
+
+fn search_interactive_all(dict_path: Option<PathBuf>, options: &SearchOptions) -> Result<i32> {
+    // SearchServiceを初期化
+    let loader = data_loader(dict_path.clone())?;
+    let search_service = load_search_service(dict_path, options.from, options.style())?
+        .ja_script(options.ja_script)
+        .form_filter(options.forms);
+
     // インタラクティブセレクターを作成
     let selector = InteractiveSelector::new(search_service.clone())
-        .show_sprite(show_sprite)
-        .play_cry(play_cry);
+        .languages(options.from, options.to)
+        .show_sprite(options.show_sprite, &loader)
+        .play_cry(options.play_cry, &loader);
+    #[cfg(feature = "sprites")]
+    let selector = selector
+        .sprite_variant(options.sprite_variant)
+        .animate(options.animate)
+        .sprite_size(options.sprite_width, options.sprite_height)
+        .sprite_renderer(options.sprite_renderer)
+        .version_group(options.version_group.clone());
 
     // 全候補から選択
     match selector.select_from_all()? {
         Some(english_name) => {
-            // 成功: 英名を標準出力
-            println!("{}", english_name);
+            // 成功: 結果を標準出力
+            let entry = search_service.entry_by_en(&english_name);
+            print_record(options.format, "", entry, options.style());
 
             // スプライト表示
             #[cfg(feature = "sprites")]
             {
-                if show_sprite {
-                    display_sprite_for_pokemon(&english_name, &search_service)?;
+                if options.show_sprite {
+                    display_sprite_for_pokemon(&english_name, &loader, options)?;
                 }
             }
 
@@ -195,6 +777,387 @@ fn search_interactive_all(
     }
 }
 
+fn handle_list(
+    dict_path: Option<PathBuf>,
+    filter: &ListFilter,
+    format: OutputFormat,
+    style: NameStyle,
+) -> Result<i32> {
+    let search_service = load_search_service(dict_path, Language::Ja, style)?;
+
+    let entries = list::list_entries(&search_service, filter);
+    if entries.is_empty() {
+        eprintln!("条件に合うポケモンがいませんでした");
+        return Ok(2);
+    }
+
+    if let Some(header) = format.header() {
+        println!("{}", header);
+    }
+    for entry in entries {
+        if let Some(record) = format.record("", Some(entry), style) {
+            println!("{}", record);
+        }
+    }
+    Ok(0)
+}
+
+#[cfg(feature = "sprites")]
+fn handle_compare(
+    dict_path: Option<PathBuf>,
+    names: &[String],
+    from: Language,
+    to: Language,
+    variant: sprite::SpriteVariant,
+    sprite_width: Option<u32>,
+    sprite_height: Option<u32>,
+) -> Result<i32> {
+    use compare::CompareColumn;
+    use info::PokemonInfoService;
+    use sprite::{SpriteFile, SpriteService};
+
+    let loader = data_loader(dict_path.clone())?;
+    let search_service = load_search_service(dict_path, from, NameStyle::new(to, None))?;
+
+    // 1つでも引けなければ比べられないので、全部を確かめてから終える
+    let mut entries = Vec::new();
+    let mut missing = false;
+    for name in names {
+        match resolve_entry(&search_service, name, to) {
+            Some(entry) => entries.push(entry),
+            None => {
+                eprintln!("候補が見つかりませんでした: {}", name);
+                print_suggestions(&search_service, name, from, to);
+                missing = true;
+            }
+        }
+    }
+    if missing {
+        return Ok(2);
+    }
+
+    // 列の幅に収まる大きさで描く。指定があればそちらを優先
+    let mut sprite_service = SpriteService::with_loader(&loader).ok();
+    if let Some(ref mut service) = sprite_service {
+        service.set_size(
+            sprite_width.or(Some(compare::SPRITE_WIDTH)),
+            sprite_height.or(Some(compare::SPRITE_HEIGHT)),
+        );
+    }
+    let info_service = PokemonInfoService::with_loader(&loader).ok();
+
+    let columns: Vec<CompareColumn> = entries
+        .iter()
+        .map(|entry| {
+            let sprite = sprite_service
+                .as_ref()
+                .zip(entry.id)
+                .and_then(|(service, id)| {
+                    match service.fetch_with_fallback(id, variant, false).ok()?.1 {
+                        SpriteFile::Still(path) => service.render_sprite(&path).ok(),
+                        SpriteFile::Animated(_) => None,
+                    }
+                })
+                .unwrap_or_default();
+            CompareColumn {
+                id: entry.id,
+                japanese: entry.ja.clone(),
+                english: entry.en.clone(),
+                sprite,
+                info: info_service.as_ref().and_then(|s| s.fetch(&entry.en)),
+            }
+        })
+        .collect();
+
+    print!("{}", compare::format_comparison(&columns));
+    Ok(0)
+}
+
+/// サブコマンドの引数を1件に決める。日本語名・ローマ字・英名の完全一致を優先し、
+/// 無ければ綴り違いの最上位候補を採って、読み替えたことを標準エラーに出す
+#[cfg(feature = "sprites")]
+fn resolve_entry<'a>(
+    search_service: &'a SearchService,
+    name: &str,
+    to: Language,
+) -> Option<&'a NameEntry> {
+    search_service
+        .entry_exact(name)
+        .or_else(|| search_service.entry_by_en(name))
+        .or_else(|| {
+            let entry = search_service.best_match(name)?;
+            eprintln!(
+                "「{}」を {} として扱います",
+                name,
+                search_service.name_in(&entry.ja, to).unwrap_or(&entry.ja)
+            );
+            Some(entry)
+        })
+}
+
+#[cfg(feature = "sprites")]
+fn handle_moves(
+    dict_path: Option<PathBuf>,
+    name: &str,
+    from: Language,
+    to: Language,
+    version_group: Option<&str>,
+) -> Result<i32> {
+    use info::PokemonInfoService;
+
+    let loader = data_loader(dict_path.clone())?;
+    let search_service = load_search_service(dict_path, from, NameStyle::new(to, None))?;
+    let Some(entry) = resolve_entry(&search_service, name, to) else {
+        eprintln!("候補が見つかりませんでした: {}", name);
+        print_suggestions(&search_service, name, from, to);
+        return Ok(2);
+    };
+
+    let info_service = PokemonInfoService::with_loader(&loader)?;
+    let learnset = info_service
+        .fetch_learnset(&entry.en)
+        .with_context(|| format!("Failed to fetch moves for {}", entry.en))?;
+
+    // 指定の作品に出ていなければ、選べる作品を添えて終える
+    let Some(version_group) = version_group.or_else(|| learnset.default_version_group()) else {
+        eprintln!("{} の覚えるわざが見つかりませんでした", entry.ja);
+        return Ok(2);
+    };
+    if !learnset.version_groups().contains(&version_group) {
+        eprintln!(
+            "{} は {} に出ていません。選べる作品: {}",
+            entry.ja,
+            version_group,
+            learnset.version_groups().join(", ")
+        );
+        return Ok(2);
+    }
+
+    println!(
+        "{}",
+        info::format_header(entry.id, Some(&entry.ja), &entry.en).trim()
+    );
+    print!("{}", info_service.format_moves(&learnset, version_group));
+    Ok(0)
+}
+
+/// prefetch の失敗を種類ごとに何件まで個別に表示するか（オフラインだと全件失敗する）
+const PREFETCH_FAILURE_LIMIT: usize = 10;
+
+fn handle_prefetch(dict_path: Option<PathBuf>, only: &[CacheKind], jobs: usize) -> Result<i32> {
+    use std::io::IsTerminal;
+
+    let loader = data_loader(dict_path)?;
+    let dictionary = loader.load_dictionary()?;
+    let mut ids: Vec<u32> = dictionary.entries.iter().filter_map(|e| e.id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    #[cfg_attr(
+        not(any(feature = "sprites", feature = "cries")),
+        allow(unused_variables)
+    )]
+    let wanted = |kind| only.is_empty() || only.contains(&kind);
+
+    // タスクはサービスを借りるので、サービスを先に作っておく
+    #[cfg(feature = "sprites")]
+    let sprite_service = wanted(CacheKind::Sprites)
+        .then(|| sprite::SpriteService::with_loader(&loader))
+        .transpose()?;
+    #[cfg(feature = "cries")]
+    let cry_fetcher = wanted(CacheKind::Cries)
+        .then(cry::CryFetcher::new)
+        .transpose()?;
+    #[cfg(feature = "sprites")]
+    let info_service = wanted(CacheKind::Info)
+        .then(|| info::PokemonInfoService::with_loader(&loader))
+        .transpose()?;
+
+    #[allow(unused_mut)] // 機能を切ったビルドでは push されない
+    let mut tasks: Vec<prefetch::PrefetchTask> = Vec::new();
+    #[cfg(feature = "sprites")]
+    if let Some(ref service) = sprite_service {
+        tasks.push(prefetch::PrefetchTask {
+            label: "sprites",
+            fetch: Box::new(|id| service.prefetch(id)),
+        });
+    }
+    #[cfg(feature = "cries")]
+    if let Some(ref fetcher) = cry_fetcher {
+        tasks.push(prefetch::PrefetchTask {
+            label: "cries",
+            fetch: Box::new(|id| fetcher.fetch(id)),
+        });
+    }
+    #[cfg(feature = "sprites")]
+    if let Some(ref service) = info_service {
+        tasks.push(prefetch::PrefetchTask {
+            label: "info",
+            fetch: Box::new(|id| service.prefetch(id)),
+        });
+    }
+
+    if tasks.is_empty() {
+        eprintln!("取得できる種類がありません（この機能を含めずにビルドされています）");
+        return Ok(1);
+    }
+
+    eprintln!(
+        "{} 件 × {} 種類を取得します（同時 {} 件）",
+        ids.len(),
+        tasks.len(),
+        jobs.max(1)
+    );
+
+    // 端末でなければ（ログへのリダイレクト等）途中経過は出さない
+    let show_progress = std::io::stderr().is_terminal();
+    let summaries = prefetch::Prefetcher::new(jobs).run(&ids, &tasks, |done, total| {
+        if show_progress {
+            eprint!("\r{}/{}", done, total);
+        }
+    });
+    if show_progress {
+        eprintln!();
+    }
+
+    let mut failed = 0;
+    for summary in &summaries {
+        eprintln!(
+            "{}: 取得 {} 件 / キャッシュ済み {} 件 / 失敗 {} 件",
+            summary.label,
+            summary.downloaded,
+            summary.cached,
+            summary.failed.len()
+        );
+        for (id, error) in summary.failed.iter().take(PREFETCH_FAILURE_LIMIT) {
+            eprintln!("  No.{}: {}", id, error);
+        }
+        if summary.failed.len() > PREFETCH_FAILURE_LIMIT {
+            eprintln!(
+                "  ...ほか {} 件",
+                summary.failed.len() - PREFETCH_FAILURE_LIMIT
+            );
+        }
+        failed += summary.failed.len();
+    }
+
+    if failed > 0 {
+        eprintln!("再実行すると取得できなかった分だけを取り直します");
+        Ok(2)
+    } else {
+        Ok(0)
+    }
+}
+
+fn handle_cache(dict_path: Option<PathBuf>, action: CacheAction) -> Result<i32> {
+    use cache::{CacheStore, format_size};
+
+    let store = CacheStore::new()?;
+    let selected = |only: Vec<CacheKind>| {
+        if only.is_empty() {
+            CacheKind::ALL.to_vec()
+        } else {
+            only
+        }
+    };
+
+    match action {
+        CacheAction::Stats => {
+            for kind in CacheKind::ALL {
+                let stats = store.stats(kind)?;
+                print!(
+                    "{:<8} {:>5} 件 {:>10}",
+                    kind.dir_name(),
+                    stats.entries,
+                    format_size(stats.bytes)
+                );
+                if stats.partials > 0 {
+                    print!("  （取得途中の .part {} 件）", stats.partials);
+                }
+                println!();
+            }
+            Ok(0)
+        }
+        CacheAction::Verify { only, delete } => {
+            let mut corrupt_total = 0;
+            for kind in selected(only) {
+                let Some(is_valid) = cache_validator(kind) else {
+                    eprintln!(
+                        "{}: この機能を含めずにビルドされているため検証できません",
+                        kind.dir_name()
+                    );
+                    continue;
+                };
+                let corrupt = store.verify(kind, is_valid, delete)?;
+                for path in &corrupt {
+                    println!("{}", path.display());
+                }
+                eprintln!(
+                    "{}: 壊れたファイル {} 件{}",
+                    kind.dir_name(),
+                    corrupt.len(),
+                    if delete && !corrupt.is_empty() {
+                        "（削除しました）"
+                    } else {
+                        ""
+                    }
+                );
+                corrupt_total += corrupt.len();
+            }
+            Ok(if corrupt_total > 0 && !delete { 2 } else { 0 })
+        }
+        CacheAction::Prune { dry_run } => {
+            let loader = data_loader(dict_path)?;
+            let known_ids: std::collections::HashSet<u32> = loader
+                .load_dictionary()?
+                .entries
+                .iter()
+                .filter_map(|e| e.id)
+                .collect();
+
+            for kind in CacheKind::ALL {
+                let targets = store.prune(kind, &known_ids, dry_run)?;
+                for path in &targets {
+                    println!("{}", path.display());
+                }
+                eprintln!(
+                    "{}: {} 件{}",
+                    kind.dir_name(),
+                    targets.len(),
+                    if dry_run {
+                        "が削除対象です"
+                    } else {
+                        "を削除しました"
+                    }
+                );
+            }
+            Ok(0)
+        }
+        CacheAction::Clear { kinds, all } => {
+            let kinds = if all { CacheKind::ALL.to_vec() } else { kinds };
+            for kind in kinds {
+                let removed = store.clear(kind)?;
+                eprintln!("{}: {} 件を削除しました", kind.dir_name(), removed);
+            }
+            Ok(0)
+        }
+    }
+}
+
+/// 種類ごとの「読めるか」の判定。その機能を含めずにビルドしたら None
+fn cache_validator(kind: CacheKind) -> Option<fn(&std::path::Path) -> bool> {
+    match kind {
+        #[cfg(feature = "sprites")]
+        CacheKind::Sprites => Some(sprite::SpriteService::is_decodable),
+        #[cfg(feature = "sprites")]
+        CacheKind::Info => Some(info::is_valid_cache_file),
+        #[cfg(feature = "cries")]
+        CacheKind::Cries => Some(cry::is_decodable),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
 fn handle_update(
     dict_path: Option<PathBuf>,
     online: bool,
@@ -202,11 +1165,6 @@ fn handle_update(
     verify_sha256: Option<String>,
     dry_run: bool,
 ) -> Result<i32> {
-    if online {
-        eprintln!("Online update (PokéAPI crawling) is not yet implemented");
-        return Ok(1);
-    }
-
     // UpdateServiceを初期化
     let update_service = if let Some(path) = dict_path {
         UpdateService::with_path(path)?
@@ -214,8 +1172,14 @@ fn handle_update(
         UpdateService::new()?
     };
 
-    // 更新実行
-    match update_service.update(source_url, verify_sha256, dry_run) {
+    // 更新実行（--online は PokéAPI を直接クロール。生成物は署名が無いので SHA256 検証は不可）
+    let result = if online {
+        update_service.update_online(source_url, dry_run)
+    } else {
+        update_service.update(source_url, verify_sha256, dry_run)
+    };
+
+    match result {
         Ok(()) => Ok(0),
         Err(e) => {
             eprintln!("Update failed: {:?}", e);
@@ -225,18 +1189,51 @@ fn handle_update(
 }
 
 #[cfg(feature = "sprites")]
-fn display_sprite_for_pokemon(english_name: &str, _search_service: &SearchService) -> Result<()> {
+fn display_sprite_for_pokemon(
+    english_name: &str,
+    loader: &data::DataLoader,
+    options: &SearchOptions,
+) -> Result<()> {
     use crate::sprite::SpriteService;
+    use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 
-    let sprite_service = SpriteService::new()?;
-    sprite_service.display_sprite_for_pokemon(english_name)?;
+    let variant = options.sprite_variant;
+    let mut sprite_service = SpriteService::with_loader(loader)?;
+    sprite_service.set_size(options.sprite_width, options.sprite_height);
+    sprite_service.set_renderer(options.sprite_renderer);
+    let Some(displayed) =
+        sprite_service.display_variant_for_pokemon(english_name, variant, options.animate)?
+    else {
+        return Ok(());
+    };
+    let mut lines_below = 0;
+    if displayed.variant != variant {
+        eprintln!(
+            "{}の画像が無いため{}を表示しました",
+            variant.label(),
+            displayed.variant.label()
+        );
+        lines_below += 1;
+    }
+    if let Some(mut animation) = displayed.animation {
+        eprintln!("（何かキーを押すと終了）");
+        animation.add_lines_below(lines_below + 1);
+        enable_raw_mode()?;
+        let key = animation.next_key();
+        disable_raw_mode()?;
+        key?;
+    }
 
     Ok(())
 }
 
 #[cfg(not(feature = "sprites"))]
 #[allow(dead_code)]
-fn display_sprite_for_pokemon(_english_name: &str, _search_service: &SearchService) -> Result<()> {
+fn display_sprite_for_pokemon(
+    _english_name: &str,
+    _loader: &data::DataLoader,
+    _options: &SearchOptions,
+) -> Result<()> {
     eprintln!("スプライト機能は無効です。--features sprites でビルドしてください。");
     Ok(())
 }
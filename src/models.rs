@@ -1,6 +1,7 @@
+use crate::language::{JA_KANJI_CODE, Language};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// names.jsonのルート構造
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,8 +16,59 @@ pub struct NameDictionary {
     pub entries: Vec<NameEntry>,
 }
 
+/// PokéAPI の pokemon id のうち、これより大きいものはフォルム（種の id は 1〜1025）
+pub const FORM_ID_OFFSET: u32 = 10000;
+
+/// フォルムの種類。フォルムのスラッグ（mega-x / alola など）から決める
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FormCategory {
+    /// メガシンカ
+    Mega,
+    /// キョダイマックス
+    Gmax,
+    /// リージョンフォーム（アローラ・ガラル・ヒスイ・パルデア）
+    Regional,
+    /// ゲンシカイキ
+    Primal,
+    /// そのほか（ロトムのフォルム・コライドンのビルドなど）。知らない値もここに入る
+    #[serde(other)]
+    Other,
+}
+
+impl FormCategory {
+    /// フォルムのスラッグ（種名を除いた部分）から種類を決める
+    pub fn from_form_slug(form_slug: &str) -> Self {
+        let tokens: Vec<&str> = form_slug.split('-').collect();
+        if tokens.contains(&"mega") {
+            FormCategory::Mega
+        } else if tokens.contains(&"gmax") {
+            FormCategory::Gmax
+        } else if tokens
+            .iter()
+            .any(|t| matches!(*t, "alola" | "galar" | "hisui" | "paldea"))
+        {
+            FormCategory::Regional
+        } else if tokens.contains(&"primal") {
+            FormCategory::Primal
+        } else {
+            FormCategory::Other
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FormCategory::Mega => "メガシンカ",
+            FormCategory::Gmax => "キョダイマックス",
+            FormCategory::Regional => "リージョンフォーム",
+            FormCategory::Primal => "ゲンシカイキ",
+            FormCategory::Other => "そのほかのフォルム",
+        }
+    }
+}
+
 /// 個別のポケモン名エントリ
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct NameEntry {
     /// 日本語名（カタカナ）
     pub ja: String,
@@ -25,9 +77,57 @@ pub struct NameEntry {
     /// ポケモンID（スプライト表示用）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<u32>,
+    /// PokéAPI のスラッグ。種は pokemon-species の（deoxys）、フォルムは pokemon の
+    /// （charizard-mega-x）。--output slug / showdown で使う。旧データには無い
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
     /// タイプの英語スラッグ（slot 昇順）。旧データには無いので default で空
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub types: Vec<String>,
+    /// ja-Hrkt・en 以外の名前（PokéAPI の言語コードの小文字 → 名前）。v3 から。
+    /// 漢字まじりの日本語名（ja）もここに入る。旧データには無いので default で空
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub names: BTreeMap<String, String>,
+    /// 元の種の図鑑番号（種のエントリは id と同じ）。v3 から
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub species_id: Option<u32>,
+    /// フォルムを表すスラッグ（個体のスラッグから種名を除いたもの。alola / mega-x）。
+    /// 種のエントリは None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_slug: Option<String>,
+    /// 種のデフォルトの姿か。旧データには無いので id から判断する（is_form）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_default: Option<bool>,
+    /// フォルムの種類。種のエントリは None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_category: Option<FormCategory>,
+}
+
+impl NameEntry {
+    /// 指定した言語の名前。辞書に無ければ None
+    pub fn name(&self, language: Language) -> Option<&str> {
+        match language {
+            Language::Ja => Some(&self.ja),
+            Language::En => Some(&self.en),
+            other => self.names.get(other.code()).map(String::as_str),
+        }
+    }
+
+    /// フォルムのエントリか。is_default が無い旧データでは id がフォルムの範囲かで決める
+    pub fn is_form(&self) -> bool {
+        match self.is_default {
+            Some(is_default) => !is_default,
+            None => self.id.is_some_and(|id| id > FORM_ID_OFFSET),
+        }
+    }
+
+    /// 漢字まじりの日本語名（ロコン（アローラの姿） など）。カタカナ表記と同じなら None
+    pub fn ja_kanji(&self) -> Option<&str> {
+        self.names
+            .get(JA_KANJI_CODE)
+            .map(String::as_str)
+            .filter(|kanji| *kanji != self.ja)
+    }
 }
 
 impl NameDictionary {
@@ -39,6 +139,23 @@ impl NameDictionary {
             .collect()
     }
 
+    /// エントリを ja → エントリ全体の HashMap に変換（構造化出力用）
+    pub fn to_entry_map(&self) -> HashMap<String, NameEntry> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.ja.clone(), entry.clone()))
+            .collect()
+    }
+
+    /// エントリを英名（小文字）→ 日本語名の HashMap に変換（逆引き用）。
+    /// 英名の大文字小文字は入力ゆれが大きいので、キーは小文字に揃える
+    pub fn to_reverse_hashmap(&self) -> HashMap<String, String> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.en.to_lowercase(), entry.ja.clone()))
+            .collect()
+    }
+
     /// エントリを ja → types の HashMap に変換（タイプトークン生成用）
     pub fn to_type_map(&self) -> HashMap<String, Vec<String>> {
         self.entries
@@ -49,12 +166,13 @@ impl NameDictionary {
 
     /// スキーマバージョンの検証
     ///
-    /// v1（types 無し）と v2（types あり）の両方を受理する。types は
-    /// `#[serde(default)]` で空になるため、v1 データでも名前検索は動く。
-    /// これにより、配布リリースが v1 のまま新バイナリを使っても壊れない。
+    /// v1（types 無し）・v2（types あり）・v3（多言語の names あり）を受理する。
+    /// types と names は `#[serde(default)]` で空になるため、古いデータでも
+    /// 日本語・英語の名前検索は動く。これにより、配布リリースが古いまま
+    /// 新バイナリを使っても壊れない。
     pub fn validate_schema(&self) -> Result<(), String> {
         const MIN_SCHEMA_VERSION: u32 = 1;
-        const MAX_SCHEMA_VERSION: u32 = 2;
+        const MAX_SCHEMA_VERSION: u32 = 3;
         if !(MIN_SCHEMA_VERSION..=MAX_SCHEMA_VERSION).contains(&self.schema_version) {
             return Err(format!(
                 "Unsupported schema version: {} (supported: {}..={})",
@@ -155,6 +273,100 @@ mod tests {
         assert!(dict.entries[1].types.is_empty());
     }
 
+    #[test]
+    fn test_deserialize_names() {
+        let json = r#"{
+            "schema_version": 3,
+            "generated_at": "2025-01-01T00:00:00Z",
+            "count": 2,
+            "entries": [
+                {"ja": "ピカチュウ", "en": "Pikachu", "id": 25,
+                 "names": {"fr": "Pikachu", "ja": "ピカチュウ", "ko": "피카츄"}},
+                {"ja": "フシギダネ", "en": "Bulbasaur"}
+            ]
+        }"#;
+
+        let dict: NameDictionary = serde_json::from_str(json).unwrap();
+        assert!(dict.validate().is_ok());
+        let pikachu = &dict.entries[0];
+        assert_eq!(pikachu.name(Language::Ko), Some("피카츄"));
+        assert_eq!(pikachu.name(Language::Ja), Some("ピカチュウ"));
+        assert_eq!(pikachu.name(Language::En), Some("Pikachu"));
+        // names が無い旧形式のエントリは ja・en 以外を持たない
+        assert_eq!(dict.entries[1].name(Language::Fr), None);
+        // カタカナと同じ表記の ja は漢字名として扱わない
+        assert_eq!(pikachu.ja_kanji(), None);
+        assert!(
+            !serde_json::to_string(&dict.entries[1])
+                .unwrap()
+                .contains("names")
+        );
+    }
+
+    #[test]
+    fn test_ja_kanji() {
+        let mut entry = NameEntry {
+            ja: "ロコン（アローラのすがた）".to_string(),
+            en: "Vulpix Alola".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(entry.ja_kanji(), None);
+        entry
+            .names
+            .insert("ja".to_string(), "ロコン（アローラの姿）".to_string());
+        assert_eq!(entry.ja_kanji(), Some("ロコン（アローラの姿）"));
+        // Language::Ja はカタカナ表記のまま
+        assert_eq!(entry.name(Language::Ja), Some("ロコン（アローラのすがた）"));
+    }
+
+    #[test]
+    fn test_deserialize_form_model() {
+        let json = r#"{
+            "ja": "メガリザードンX", "en": "Charizard Mega X", "id": 10034,
+            "slug": "charizard-mega-x", "species_id": 6, "form_slug": "mega-x", "is_default": false,
+            "form_category": "mega"
+        }"#;
+        let entry: NameEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry.slug.as_deref(), Some("charizard-mega-x"));
+        assert_eq!(entry.species_id, Some(6));
+        assert_eq!(entry.form_slug.as_deref(), Some("mega-x"));
+        assert_eq!(entry.form_category, Some(FormCategory::Mega));
+        assert!(entry.is_form());
+
+        // 知らない種類は Other として読む
+        let entry: NameEntry =
+            serde_json::from_str(r#"{"ja": "A", "en": "A", "form_category": "dynamax"}"#).unwrap();
+        assert_eq!(entry.form_category, Some(FormCategory::Other));
+    }
+
+    #[test]
+    fn test_is_form_falls_back_to_id() {
+        let mut entry = NameEntry {
+            ja: "ロコン（アローラのすがた）".to_string(),
+            en: "Vulpix Alola".to_string(),
+            id: Some(10103),
+            ..Default::default()
+        };
+        assert!(entry.is_form());
+        entry.id = Some(37);
+        assert!(!entry.is_form());
+        // is_default があればそちらを信じる
+        entry.is_default = Some(false);
+        assert!(entry.is_form());
+    }
+
+    #[test]
+    fn test_form_category_from_form_slug() {
+        assert_eq!(FormCategory::from_form_slug("mega-x"), FormCategory::Mega);
+        assert_eq!(FormCategory::from_form_slug("gmax"), FormCategory::Gmax);
+        assert_eq!(
+            FormCategory::from_form_slug("paldea-combat-breed"),
+            FormCategory::Regional
+        );
+        assert_eq!(FormCategory::from_form_slug("primal"), FormCategory::Primal);
+        assert_eq!(FormCategory::from_form_slug("heat"), FormCategory::Other);
+    }
+
     #[test]
     fn test_to_hashmap() {
         let dict = NameDictionary {
@@ -167,12 +379,14 @@ mod tests {
                     en: "Pikachu".to_string(),
                     id: None,
                     types: vec![],
+                    ..Default::default()
                 },
                 NameEntry {
                     ja: "フシギダネ".to_string(),
                     en: "Bulbasaur".to_string(),
                     id: None,
                     types: vec![],
+                    ..Default::default()
                 },
             ],
         };
@@ -182,6 +396,26 @@ mod tests {
         assert_eq!(map.get("フシギダネ"), Some(&"Bulbasaur".to_string()));
     }
 
+    #[test]
+    fn test_to_reverse_hashmap() {
+        let dict = NameDictionary {
+            schema_version: 2,
+            generated_at: Utc::now(),
+            count: 1,
+            entries: vec![NameEntry {
+                ja: "ミスター・バリヤー".to_string(),
+                en: "Mr. Mime".to_string(),
+                id: Some(122),
+                types: vec![],
+                ..Default::default()
+            }],
+        };
+
+        let map = dict.to_reverse_hashmap();
+        assert_eq!(map.get("mr. mime"), Some(&"ミスター・バリヤー".to_string()));
+        assert_eq!(map.get("Mr. Mime"), None);
+    }
+
     #[test]
     fn test_to_type_map() {
         let dict = NameDictionary {
@@ -193,6 +427,7 @@ mod tests {
                 en: "Charizard".to_string(),
                 id: Some(6),
                 types: vec!["fire".to_string(), "flying".to_string()],
+                ..Default::default()
             }],
         };
 
@@ -220,8 +455,12 @@ mod tests {
         dict.schema_version = 1;
         assert!(dict.validate_schema().is_ok());
 
-        // 未知の新バージョンは拒否
+        // v3（多言語の names 付き）も OK
         dict.schema_version = 3;
+        assert!(dict.validate_schema().is_ok());
+
+        // 未知の新バージョンは拒否
+        dict.schema_version = 4;
         assert!(dict.validate_schema().is_err());
 
         // 0 も拒否
@@ -241,12 +480,14 @@ mod tests {
                     en: "Pikachu".to_string(),
                     id: None,
                     types: vec![],
+                    ..Default::default()
                 },
                 NameEntry {
                     ja: "フシギダネ".to_string(),
                     en: "Bulbasaur".to_string(),
                     id: None,
                     types: vec![],
+                    ..Default::default()
                 },
             ],
         };
@@ -262,6 +503,7 @@ mod tests {
                 en: "Pikachu".to_string(),
                 id: None,
                 types: vec![],
+                ..Default::default()
             }],
         };
 
@@ -279,6 +521,7 @@ mod tests {
                 en: "Pikachu".to_string(),
                 id: None,
                 types: vec![],
+                ..Default::default()
             }],
         };
 
@@ -296,6 +539,7 @@ mod tests {
                 en: "Pikachu".to_string(),
                 id: None,
                 types: vec![],
+                ..Default::default()
             }],
         };
 
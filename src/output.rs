@@ -0,0 +1,471 @@
+//! 検索結果の出力形式（--format）。
+//!
+//! 既定の text は従来どおり名前だけを1行で出す（Pokemiro 連携用）。
+//! 出す名前の言語は --to で選ぶ。--output を付けると名前の代わりに
+//! PokéAPI のスラッグ・Showdown の ID・図鑑番号を出す。対話選択は英名で確定し、
+//! 結果を print_record で出すときにここで --output を効かせる。
+//! json / tsv / csv はスクリプトから使うための構造化出力で、
+//! 見つからなかった入力も status 付きの1レコードとして出す。
+
+use crate::language::Language;
+use crate::models::NameEntry;
+use crate::search::fold_diacritics;
+use clap::ValueEnum;
+
+/// 出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// 名前のみ（既定）
+    #[default]
+    Text,
+    /// 1件1行の JSON（JSON Lines）
+    Json,
+    /// タブ区切り（ヘッダー行付き）
+    Tsv,
+    /// カンマ区切り（ヘッダー行付き）
+    Csv,
+}
+
+/// text で名前の代わりに出すもの（--output）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputName {
+    /// 英名（--to によらない）
+    En,
+    /// PokéAPI のスラッグ（charizard-mega-x）
+    Slug,
+    /// Showdown の ID（charizardmegax）
+    Showdown,
+    /// 図鑑番号。フォルムは PokéAPI の pokemon id（10034 など）
+    Id,
+}
+
+/// text で出す1件分の文字列の決め方。--output が無ければ --to の言語の名前
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NameStyle {
+    pub to: Language,
+    pub output: Option<OutputName>,
+}
+
+impl NameStyle {
+    pub fn new(to: Language, output: Option<OutputName>) -> Self {
+        Self { to, output }
+    }
+
+    /// その言語の名前が無いエントリ（一部のフォルム）は英名で代える
+    pub fn text(self, entry: &NameEntry) -> String {
+        match self.output {
+            None => entry.name(self.to).unwrap_or(&entry.en).to_string(),
+            Some(OutputName::En) => entry.en.clone(),
+            Some(OutputName::Slug) => slug(entry),
+            Some(OutputName::Showdown) => showdown_id(entry),
+            // id の無い辞書は load_search_service で止める。エントリ単位で欠けていれば英名
+            Some(OutputName::Id) => entry
+                .id
+                .map_or_else(|| entry.en.clone(), |id| id.to_string()),
+        }
+    }
+
+    /// 辞書に slug が要るか（slug・Showdown の ID はスラッグから作る）
+    pub fn needs_slug(self) -> bool {
+        matches!(
+            self.output,
+            Some(OutputName::Slug) | Some(OutputName::Showdown)
+        )
+    }
+
+    /// 辞書に id が要るか
+    pub fn needs_id(self) -> bool {
+        self.output == Some(OutputName::Id)
+    }
+}
+
+/// スラッグ。slug を持たないエントリは英名から組み立てる（Mr. Mime → mr-mime）
+fn slug(entry: &NameEntry) -> String {
+    if let Some(ref slug) = entry.slug {
+        return slug.clone();
+    }
+    fold_diacritics(&entry.en)
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .map(|word| {
+            word.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// フォルム名が PokéAPI と Showdown で違うもの（スラッグ → Showdown の ID）。
+/// 性別・ピカチュウの帽子・メテオナミノルは showdown_id で規則的に直す
+const SHOWDOWN_IDS: &[(&str, &str)] = &[
+    ("necrozma-dusk", "necrozmaduskmane"),
+    ("necrozma-dawn", "necrozmadawnwings"),
+    ("toxtricity-amped", "toxtricity"),
+    ("toxtricity-amped-gmax", "toxtricitygmax"),
+    ("urshifu-single-strike", "urshifu"),
+    ("urshifu-single-strike-gmax", "urshifugmax"),
+    ("greninja-battle-bond", "greninjabond"),
+    ("zygarde-50", "zygarde"),
+    ("zygarde-50-power-construct", "zygarde"),
+    ("zygarde-10-power-construct", "zygarde10"),
+    ("darmanitan-galar-standard", "darmanitangalar"),
+    ("tauros-paldea-combat-breed", "taurospaldeacombat"),
+    ("tauros-paldea-blaze-breed", "taurospaldeablaze"),
+    ("tauros-paldea-aqua-breed", "taurospaldeaaqua"),
+    ("squawkabilly-blue-plumage", "squawkabillyblue"),
+    ("squawkabilly-yellow-plumage", "squawkabillyyellow"),
+    ("squawkabilly-white-plumage", "squawkabillywhite"),
+    ("ogerpon-wellspring-mask", "ogerponwellspring"),
+    ("ogerpon-hearthflame-mask", "ogerponhearthflame"),
+    ("ogerpon-cornerstone-mask", "ogerponcornerstone"),
+];
+
+/// Showdown の ID。名前の違うフォルムは SHOWDOWN_IDS と下の規則で直し、
+/// それ以外はスラッグから英数字以外を除く
+/// （種のスラッグは pokemon-species 側なので deoxys-normal ではなく deoxys になる）
+fn showdown_id(entry: &NameEntry) -> String {
+    let slug = slug(entry);
+    if let Some((_, id)) = SHOWDOWN_IDS.iter().find(|(s, _)| *s == slug) {
+        return id.to_string();
+    }
+    let name = if let Some(base) = slug.strip_suffix("-female") {
+        // meowstic-female → meowsticf（オスは無印）
+        format!("{}f", base)
+    } else if let Some(base) = slug.strip_suffix("-male") {
+        base.to_string()
+    } else if slug.starts_with("minior-") {
+        // 色は見た目だけなので、流星か否かだけ残す
+        if slug.ends_with("-meteor") {
+            "minior-meteor".to_string()
+        } else {
+            "minior".to_string()
+        }
+    } else if slug.starts_with("pikachu-") && slug.ends_with("-cap") {
+        // pikachu-original-cap → pikachuoriginal
+        slug.trim_end_matches("-cap").to_string()
+    } else {
+        slug
+    };
+    name.chars().filter(char::is_ascii_alphanumeric).collect()
+}
+
+/// 表形式の列。JSON のキーとも揃える
+const COLUMNS: [&str; 6] = ["query", "status", "ja", "en", "id", "types"];
+
+/// 表形式で types を1セルに収めるときの区切り
+const TYPE_SEPARATOR: &str = "/";
+
+impl OutputFormat {
+    /// 名前だけを出す形式か。text 以外は見つからなかった入力もレコードとして出す
+    pub fn is_text(self) -> bool {
+        self == OutputFormat::Text
+    }
+
+    /// 表形式の先頭に出すヘッダー行。text / json は持たない
+    pub fn header(self) -> Option<String> {
+        let cells = COLUMNS.map(str::to_string);
+        match self {
+            OutputFormat::Tsv => Some(tsv_row(&cells)),
+            OutputFormat::Csv => Some(csv_row(&cells)),
+            OutputFormat::Text | OutputFormat::Json => None,
+        }
+    }
+
+    /// 1件分の出力行。text で見つからなかった場合だけ None（何も出さない）。
+    /// `style` は text のときに何を出すかだけに効く
+    pub fn record(
+        self,
+        query: &str,
+        entry: Option<&NameEntry>,
+        style: NameStyle,
+    ) -> Option<String> {
+        match self {
+            OutputFormat::Text => entry.map(|e| style.text(e)),
+            OutputFormat::Json => Some(json_record(query, entry)),
+            OutputFormat::Tsv => Some(tsv_row(&cells(query, entry))),
+            OutputFormat::Csv => Some(csv_row(&cells(query, entry))),
+        }
+    }
+}
+
+fn status(entry: Option<&NameEntry>) -> &'static str {
+    if entry.is_some() { "ok" } else { "not_found" }
+}
+
+/// ja・en 以外の名前とスラッグは v3 の辞書にしか無いので、あるときだけ足す
+fn json_record(query: &str, entry: Option<&NameEntry>) -> String {
+    let value = match entry {
+        Some(e) => {
+            let mut value = serde_json::json!({
+                "query": query,
+                "status": status(entry),
+                "ja": e.ja,
+                "en": e.en,
+                "id": e.id,
+                "types": e.types,
+            });
+            if let Some(ref slug) = e.slug {
+                value["slug"] = serde_json::json!(slug);
+            }
+            if !e.names.is_empty() {
+                value["names"] = serde_json::json!(e.names);
+            }
+            value
+        }
+        None => serde_json::json!({
+            "query": query,
+            "status": status(entry),
+        }),
+    };
+    value.to_string()
+}
+
+fn cells(query: &str, entry: Option<&NameEntry>) -> [String; 6] {
+    match entry {
+        Some(e) => [
+            query.to_string(),
+            status(entry).to_string(),
+            e.ja.clone(),
+            e.en.clone(),
+            e.id.map(|id| id.to_string()).unwrap_or_default(),
+            e.types.join(TYPE_SEPARATOR),
+        ],
+        None => [
+            query.to_string(),
+            status(entry).to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ],
+    }
+}
+
+/// TSV はエスケープの取り決めが無いので、区切りを壊す文字は空白に潰す
+fn tsv_row(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|c| c.replace(['\t', '\n', '\r'], " "))
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+/// RFC 4180 に従い、区切り・引用符・改行を含むセルだけを引用する
+fn csv_row(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|c| {
+            if c.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", c.replace('"', "\"\""))
+            } else {
+                c.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lang(to: Language) -> NameStyle {
+        NameStyle::new(to, None)
+    }
+
+    fn charizard() -> NameEntry {
+        NameEntry {
+            ja: "リザードン".to_string(),
+            en: "Charizard".to_string(),
+            id: Some(6),
+            types: vec!["fire".to_string(), "flying".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_text_record() {
+        let entry = charizard();
+        assert_eq!(
+            OutputFormat::Text.record("リザードン", Some(&entry), lang(Language::En)),
+            Some("Charizard".to_string())
+        );
+        // 逆引きでは日本語名を出す
+        assert_eq!(
+            OutputFormat::Text.record("charizard", Some(&entry), lang(Language::Ja)),
+            Some("リザードン".to_string())
+        );
+        // ほかの言語は names から。無ければ英名で代える
+        let mut entry = entry;
+        entry
+            .names
+            .insert("fr".to_string(), "Dracaufeu".to_string());
+        assert_eq!(
+            OutputFormat::Text.record("リザードン", Some(&entry), lang(Language::Fr)),
+            Some("Dracaufeu".to_string())
+        );
+        assert_eq!(
+            OutputFormat::Text.record("リザードン", Some(&entry), lang(Language::Ko)),
+            Some("Charizard".to_string())
+        );
+        assert_eq!(
+            OutputFormat::Text.record("ミュウ", None, lang(Language::En)),
+            None
+        );
+        assert_eq!(OutputFormat::Text.header(), None);
+    }
+
+    #[test]
+    fn test_text_record_with_output_name() {
+        let mut entry = NameEntry {
+            ja: "メガリザードンX".to_string(),
+            en: "Mega Charizard X".to_string(),
+            id: Some(10034),
+            slug: Some("charizard-mega-x".to_string()),
+            ..Default::default()
+        };
+        let record = |output, entry: &NameEntry| {
+            OutputFormat::Text.record("", Some(entry), NameStyle::new(Language::Ja, Some(output)))
+        };
+        // --to によらず --output が優先
+        assert_eq!(record(OutputName::En, &entry).unwrap(), "Mega Charizard X");
+        assert_eq!(
+            record(OutputName::Slug, &entry).unwrap(),
+            "charizard-mega-x"
+        );
+        assert_eq!(
+            record(OutputName::Showdown, &entry).unwrap(),
+            "charizardmegax"
+        );
+        assert_eq!(record(OutputName::Id, &entry).unwrap(), "10034");
+
+        // slug の無い旧データは英名から組み立てる
+        entry.en = "Mr. Mime".to_string();
+        entry.slug = None;
+        entry.id = None;
+        assert_eq!(record(OutputName::Slug, &entry).unwrap(), "mr-mime");
+        assert_eq!(record(OutputName::Showdown, &entry).unwrap(), "mrmime");
+        assert_eq!(record(OutputName::Id, &entry).unwrap(), "Mr. Mime");
+        entry.en = "Flabébé".to_string();
+        assert_eq!(record(OutputName::Showdown, &entry).unwrap(), "flabebe");
+    }
+
+    #[test]
+    fn test_showdown_id_exceptions() {
+        let showdown = |slug: &str| {
+            showdown_id(&NameEntry {
+                slug: Some(slug.to_string()),
+                ..Default::default()
+            })
+        };
+        assert_eq!(showdown("meowstic-female"), "meowsticf");
+        assert_eq!(showdown("meowstic-male"), "meowstic");
+        assert_eq!(showdown("indeedee-female"), "indeedeef");
+        assert_eq!(showdown("necrozma-dusk"), "necrozmaduskmane");
+        assert_eq!(showdown("minior-red-meteor"), "miniormeteor");
+        assert_eq!(showdown("minior-blue"), "minior");
+        assert_eq!(showdown("pikachu-original-cap"), "pikachuoriginal");
+        assert_eq!(showdown("toxtricity-amped-gmax"), "toxtricitygmax");
+        assert_eq!(showdown("toxtricity-low-key-gmax"), "toxtricitylowkeygmax");
+        // 規則に当たらないものはスラッグから作る
+        assert_eq!(showdown("pikachu-rock-star"), "pikachurockstar");
+        assert_eq!(showdown("nidoran-f"), "nidoranf");
+    }
+
+    #[test]
+    fn test_json_record() {
+        let entry = charizard();
+        let found: serde_json::Value = serde_json::from_str(
+            &OutputFormat::Json
+                .record("リザードン", Some(&entry), lang(Language::En))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            found,
+            serde_json::json!({
+                "query": "リザードン",
+                "status": "ok",
+                "ja": "リザードン",
+                "en": "Charizard",
+                "id": 6,
+                "types": ["fire", "flying"],
+            })
+        );
+
+        let mut entry = entry;
+        entry.names.insert("ko".to_string(), "리자몽".to_string());
+        let with_names: serde_json::Value = serde_json::from_str(
+            &OutputFormat::Json
+                .record("리자몽", Some(&entry), lang(Language::En))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(with_names["names"], serde_json::json!({"ko": "리자몽"}));
+        assert!(with_names.get("slug").is_none());
+
+        entry.slug = Some("charizard".to_string());
+        let with_slug: serde_json::Value = serde_json::from_str(
+            &OutputFormat::Json
+                .record("リザードン", Some(&entry), lang(Language::En))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(with_slug["slug"], "charizard");
+
+        let missing: serde_json::Value = serde_json::from_str(
+            &OutputFormat::Json
+                .record("ミュウ", None, lang(Language::En))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            missing,
+            serde_json::json!({"query": "ミュウ", "status": "not_found"})
+        );
+    }
+
+    #[test]
+    fn test_tsv_record() {
+        let entry = charizard();
+        assert_eq!(
+            OutputFormat::Tsv.header().unwrap(),
+            "query\tstatus\tja\ten\tid\ttypes"
+        );
+        assert_eq!(
+            OutputFormat::Tsv
+                .record("リザードン", Some(&entry), lang(Language::En))
+                .unwrap(),
+            "リザードン\tok\tリザードン\tCharizard\t6\tfire/flying"
+        );
+        assert_eq!(
+            OutputFormat::Tsv
+                .record("ミュ\tウ", None, lang(Language::En))
+                .unwrap(),
+            "ミュ ウ\tnot_found\t\t\t\t"
+        );
+    }
+
+    #[test]
+    fn test_csv_record_quotes_when_needed() {
+        let entry = NameEntry {
+            ja: "ミスター・バリヤー".to_string(),
+            en: "Mr. \"Mime\", Jr".to_string(),
+            id: None,
+            types: vec![],
+            ..Default::default()
+        };
+        assert_eq!(
+            OutputFormat::Csv.header().unwrap(),
+            "query,status,ja,en,id,types"
+        );
+        assert_eq!(
+            OutputFormat::Csv
+                .record("ミスター・バリヤー", Some(&entry), lang(Language::En))
+                .unwrap(),
+            "ミスター・バリヤー,ok,ミスター・バリヤー,\"Mr. \"\"Mime\"\", Jr\",,"
+        );
+    }
+}
@@ -24,6 +24,21 @@ pub fn type_ja(slug: &str) -> Option<&'static str> {
     })
 }
 
+/// 18タイプの英語スラッグ（図鑑の並び順）
+pub const TYPE_SLUGS: [&str; 18] = [
+    "normal", "fire", "water", "electric", "grass", "ice", "fighting", "poison", "ground",
+    "flying", "psychic", "bug", "rock", "ghost", "dragon", "dark", "steel", "fairy",
+];
+
+/// 日本語名（ひらがな・カタカナどちらでも）または英語スラッグ → スラッグ。
+/// 英語は大文字小文字を区別しない
+pub fn type_slug(name: &str) -> Option<&'static str> {
+    let name = crate::search::normalize(name.trim());
+    TYPE_SLUGS.into_iter().find(|slug| {
+        *slug == name || type_ja(slug).is_some_and(|ja| crate::search::normalize(ja) == name)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -34,4 +49,16 @@ mod tests {
         assert_eq!(type_ja("flying"), Some("ひこう"));
         assert_eq!(type_ja("stellar"), None);
     }
+
+    #[test]
+    fn test_type_slug() {
+        assert_eq!(type_slug("ほのお"), Some("fire"));
+        assert_eq!(type_slug("ホノオ"), Some("fire"));
+        assert_eq!(type_slug("エスパー"), Some("psychic"));
+        assert_eq!(type_slug("えすぱー"), Some("psychic"));
+        assert_eq!(type_slug("Flying"), Some("flying"));
+        assert_eq!(type_slug("stellar"), None);
+        // 全スラッグが日本語名を持つ
+        assert!(TYPE_SLUGS.iter().all(|slug| type_ja(slug).is_some()));
+    }
 }
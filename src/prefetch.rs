@@ -0,0 +1,215 @@
+//! スプライト・鳴き声・情報のキャッシュをまとめて埋める（prefetch サブコマンド）。
+//!
+//! 会場がオフラインでもデモできるよう、辞書の全 id について事前に取得しておく。
+//! 各キャッシュは「既にあれば取得しない」ので、中断しても再実行すれば
+//! 続きから埋まる。取得は種類と id の組ごとに、上限付きの並列数で行う。
+
+use anyhow::Result;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// 既定の並列数。raw.githubusercontent.com / PokéAPI に負荷をかけすぎない程度
+pub const DEFAULT_JOBS: usize = 4;
+
+/// 1件あたりの試行回数（初回含む）
+const MAX_ATTEMPTS: u32 = 3;
+
+/// 1回目の再試行までの待ち時間。以降は倍にしていく
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// 1種類のキャッシュの取得方法。`fetch` は取得したら true、既にあれば false
+pub struct PrefetchTask<'a> {
+    pub label: &'static str,
+    pub fetch: Box<dyn Fn(u32) -> Result<bool> + Sync + 'a>,
+}
+
+/// 1種類分の結果
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PrefetchSummary {
+    pub label: &'static str,
+    /// 今回取得した件数
+    pub downloaded: usize,
+    /// 既にキャッシュにあった件数
+    pub cached: usize,
+    /// 再試行しても取得できなかった id とエラー
+    pub failed: Vec<(u32, String)>,
+}
+
+pub struct Prefetcher {
+    jobs: usize,
+    retry_delay: Duration,
+}
+
+impl Prefetcher {
+    pub fn new(jobs: usize) -> Self {
+        Self {
+            jobs: jobs.max(1),
+            retry_delay: RETRY_BASE_DELAY,
+        }
+    }
+
+    /// 全 id × 全種類を取得し、種類ごとの結果を tasks と同じ順で返す。
+    /// `progress` は1件終わるたびに (完了数, 総数) で呼ばれる
+    pub fn run<P>(&self, ids: &[u32], tasks: &[PrefetchTask], progress: P) -> Vec<PrefetchSummary>
+    where
+        P: Fn(usize, usize) + Sync,
+    {
+        let units: Vec<(usize, u32)> = (0..tasks.len())
+            .flat_map(|task| ids.iter().map(move |id| (task, *id)))
+            .collect();
+        let total = units.len();
+
+        let summaries = Mutex::new(
+            tasks
+                .iter()
+                .map(|task| PrefetchSummary {
+                    label: task.label,
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>(),
+        );
+        let next = AtomicUsize::new(0);
+        let done = AtomicUsize::new(0);
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.jobs.min(total) {
+                scope.spawn(|| {
+                    loop {
+                        let i = next.fetch_add(1, Ordering::SeqCst);
+                        let Some(&(task, id)) = units.get(i) else {
+                            break;
+                        };
+                        let result = self.with_retry(|| (tasks[task].fetch)(id));
+                        if let Ok(mut summaries) = summaries.lock() {
+                            let summary = &mut summaries[task];
+                            match result {
+                                Ok(true) => summary.downloaded += 1,
+                                Ok(false) => summary.cached += 1,
+                                Err(e) => summary.failed.push((id, format!("{:#}", e))),
+                            }
+                        }
+                        progress(done.fetch_add(1, Ordering::SeqCst) + 1, total);
+                    }
+                });
+            }
+        });
+
+        let mut summaries = summaries.into_inner().unwrap_or_default();
+        for summary in &mut summaries {
+            summary.failed.sort();
+        }
+        summaries
+    }
+
+    /// 失敗したら待ち時間を倍にしながら MAX_ATTEMPTS 回まで試す
+    fn with_retry<F>(&self, fetch: F) -> Result<bool>
+    where
+        F: Fn() -> Result<bool>,
+    {
+        let mut delay = self.retry_delay;
+        let mut attempt = 1;
+        loop {
+            match fetch() {
+                Ok(downloaded) => return Ok(downloaded),
+                Err(e) if attempt >= MAX_ATTEMPTS => return Err(e),
+                Err(_) => {
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub fn for_test(jobs: usize) -> Self {
+        Self {
+            jobs,
+            retry_delay: Duration::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_run_counts_downloaded_and_cached() {
+        let cached = [1, 3];
+        let tasks = [PrefetchTask {
+            label: "sprites",
+            fetch: Box::new(|id| Ok(!cached.contains(&id))),
+        }];
+
+        let summaries = Prefetcher::for_test(2).run(&[1, 2, 3, 4], &tasks, |_, _| {});
+
+        assert_eq!(
+            summaries,
+            vec![PrefetchSummary {
+                label: "sprites",
+                downloaded: 2,
+                cached: 2,
+                failed: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_run_retries_then_reports_failures() {
+        let attempts = Mutex::new(HashMap::<u32, u32>::new());
+        let tasks = [PrefetchTask {
+            label: "cries",
+            fetch: Box::new(|id| {
+                let mut attempts = attempts.lock().unwrap();
+                let n = attempts.entry(id).or_insert(0);
+                *n += 1;
+                match id {
+                    // 2回目で成功
+                    1 if *n < 2 => Err(anyhow::anyhow!("temporary")),
+                    // 何度やっても失敗
+                    2 => Err(anyhow::anyhow!("HTTP 404")),
+                    _ => Ok(true),
+                }
+            }),
+        }];
+
+        let summaries = Prefetcher::for_test(1).run(&[1, 2], &tasks, |_, _| {});
+
+        assert_eq!(summaries[0].downloaded, 1);
+        assert_eq!(summaries[0].failed, vec![(2, "HTTP 404".to_string())]);
+        drop(tasks);
+        let attempts = attempts.into_inner().unwrap();
+        assert_eq!(attempts[&1], 2);
+        assert_eq!(attempts[&2], MAX_ATTEMPTS);
+    }
+
+    #[test]
+    fn test_run_reports_progress_for_every_unit() {
+        let tasks = [
+            PrefetchTask {
+                label: "sprites",
+                fetch: Box::new(|_| Ok(true)),
+            },
+            PrefetchTask {
+                label: "info",
+                fetch: Box::new(|_| Ok(false)),
+            },
+        ];
+        let seen = Mutex::new(Vec::new());
+
+        let summaries = Prefetcher::for_test(3).run(&[1, 2, 3], &tasks, |done, total| {
+            seen.lock().unwrap().push((done, total));
+        });
+
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(seen, (1..=6).map(|done| (done, 6)).collect::<Vec<_>>());
+        assert_eq!(summaries[0].label, "sprites");
+        assert_eq!(summaries[0].downloaded, 3);
+        assert_eq!(summaries[1].label, "info");
+        assert_eq!(summaries[1].cached, 3);
+    }
+}
@@ -0,0 +1,371 @@
+//! スプライトを Unicode の半角ブロック（▀ ▄）と SGR の色指定で文字列にする。
+//!
+//! 1文字で上下2ピクセルを表す（上を前景色の ▀、下を背景色）。端末の画像
+//! プロトコルに頼らないので、色が出る端末ならどこでも同じ見た目になる。
+//! PokéAPI のスプライトは 96×96 の大半が透明なので、不透明な部分だけを切り出す。
+//! 端末に触らない純粋な関数なので、出力をそのままテストで比較できる。
+
+use image::RgbaImage;
+use image::imageops::{self, FilterType};
+
+/// これ未満のアルファ値は透明とみなす
+const ALPHA_THRESHOLD: u8 = 128;
+
+/// サイズ指定が無いときの上限（文字数）。公式アートワークなど大きい画像だけ縮める
+const DEFAULT_MAX_WIDTH: u32 = 64;
+const DEFAULT_MAX_HEIGHT: u32 = 32;
+
+/// 色の出し方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// 24bit（38;2;r;g;b）
+    TrueColor,
+    /// 256色（38;5;n）
+    Ansi256,
+}
+
+impl ColorMode {
+    /// COLORTERM が truecolor / 24bit なら 24bit、それ以外は 256色
+    pub fn detect() -> Self {
+        match std::env::var("COLORTERM") {
+            Ok(value) if value == "truecolor" || value == "24bit" => ColorMode::TrueColor,
+            _ => ColorMode::Ansi256,
+        }
+    }
+}
+
+/// 描画サイズと色。width / height は文字数で、片方だけなら縦横比を保つ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub color: ColorMode,
+}
+
+impl RenderOptions {
+    /// 色は端末から判定する
+    pub fn detect(width: Option<u32>, height: Option<u32>) -> Self {
+        Self {
+            width,
+            height,
+            color: ColorMode::detect(),
+        }
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self::detect(None, None)
+    }
+}
+
+/// 画像内の矩形（ピクセル）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Bounds {
+    /// 両方を含む最小の矩形
+    pub fn union(self, other: Bounds) -> Bounds {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = (self.x + self.width).max(other.x + other.width);
+        let bottom = (self.y + self.height).max(other.y + other.height);
+        Bounds {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        }
+    }
+}
+
+/// 不透明なピクセルを囲む最小の矩形。全部透明なら None
+pub fn opaque_bounds(img: &RgbaImage) -> Option<Bounds> {
+    let mut found: Option<(u32, u32, u32, u32)> = None;
+    for (x, y, pixel) in img.enumerate_pixels() {
+        if pixel[3] < ALPHA_THRESHOLD {
+            continue;
+        }
+        found = Some(match found {
+            None => (x, y, x, y),
+            Some((left, top, right, bottom)) => {
+                (left.min(x), top.min(y), right.max(x), bottom.max(y))
+            }
+        });
+    }
+    found.map(|(left, top, right, bottom)| Bounds {
+        x: left,
+        y: top,
+        width: right - left + 1,
+        height: bottom - top + 1,
+    })
+}
+
+/// 透明な縁を除いて描画し、1行ずつ返す（各行は色のリセットで終わる）
+pub fn render(img: &RgbaImage, options: &RenderOptions) -> Vec<String> {
+    match opaque_bounds(img) {
+        Some(bounds) => render_region(img, bounds, options),
+        None => Vec::new(),
+    }
+}
+
+/// bounds の範囲を描画する。アニメーションは全フレーム共通の範囲で描かないと大きさが揺れる
+pub fn render_region(img: &RgbaImage, bounds: Bounds, options: &RenderOptions) -> Vec<String> {
+    let cropped =
+        imageops::crop_imm(img, bounds.x, bounds.y, bounds.width, bounds.height).to_image();
+    let (width, height) = target_size(bounds.width, bounds.height, options);
+    let scaled = if (width, height) == cropped.dimensions() {
+        cropped
+    } else {
+        // ドット絵は拡大でぼかさない。縮小は潰れないよう平均を取る
+        let filter = if width > bounds.width {
+            FilterType::Nearest
+        } else {
+            FilterType::Triangle
+        };
+        imageops::resize(&cropped, width, height, filter)
+    };
+
+    (0..scaled.height())
+        .step_by(2)
+        .map(|y| {
+            let mut line = String::new();
+            for x in 0..scaled.width() {
+                let top = color_of(&scaled, x, y);
+                let bottom = if y + 1 < scaled.height() {
+                    color_of(&scaled, x, y + 1)
+                } else {
+                    None
+                };
+                line.push_str(&cell(top, bottom, options.color));
+            }
+            line.push_str("\x1b[0m");
+            line
+        })
+        .collect()
+}
+
+/// 描画後のピクセル数。1文字＝横1・縦2ピクセルなので、高さの指定は2倍で考える
+fn target_size(width: u32, height: u32, options: &RenderOptions) -> (u32, u32) {
+    let by_width = options.width.map(|w| w as f64 / width as f64);
+    let by_height = options.height.map(|h| (h * 2) as f64 / height as f64);
+    let scale = match (by_width, by_height) {
+        (Some(w), Some(h)) => w.min(h),
+        (Some(w), None) => w,
+        (None, Some(h)) => h,
+        (None, None) => 1f64
+            .min(DEFAULT_MAX_WIDTH as f64 / width as f64)
+            .min((DEFAULT_MAX_HEIGHT * 2) as f64 / height as f64),
+    };
+    let scaled = |n: u32| ((n as f64 * scale).round() as u32).max(1);
+    (scaled(width), scaled(height))
+}
+
+fn color_of(img: &RgbaImage, x: u32, y: u32) -> Option<[u8; 3]> {
+    let pixel = img.get_pixel(x, y);
+    (pixel[3] >= ALPHA_THRESHOLD).then_some([pixel[0], pixel[1], pixel[2]])
+}
+
+/// 上下2ピクセル分の1文字。透明な側は端末の背景色のまま残す
+fn cell(top: Option<[u8; 3]>, bottom: Option<[u8; 3]>, color: ColorMode) -> String {
+    match (top, bottom) {
+        (Some(top), Some(bottom)) => format!(
+            "\x1b[0;{};{}m▀",
+            sgr(38, top, color),
+            sgr(48, bottom, color)
+        ),
+        (Some(top), None) => format!("\x1b[0;{}m▀", sgr(38, top, color)),
+        (None, Some(bottom)) => format!("\x1b[0;{}m▄", sgr(38, bottom, color)),
+        (None, None) => "\x1b[0m ".to_string(),
+    }
+}
+
+/// 前景（38）・背景（48）の色指定
+fn sgr(target: u8, [r, g, b]: [u8; 3], color: ColorMode) -> String {
+    match color {
+        ColorMode::TrueColor => format!("{};2;{};{};{}", target, r, g, b),
+        ColorMode::Ansi256 => format!("{};5;{}", target, ansi256([r, g, b])),
+    }
+}
+
+/// 色コード（ESC [ … m）を剥がす。色付きの出力を文字だけで比べるテスト用
+#[cfg(test)]
+pub fn strip_sgr(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// 256色パレットで最も近い色（6×6×6 の色立方体か24段階のグレー）
+fn ansi256([r, g, b]: [u8; 3]) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let nearest_level = |c: u8| {
+        (0..LEVELS.len())
+            .min_by_key(|&i| (LEVELS[i] as i32 - c as i32).abs())
+            .unwrap_or(0)
+    };
+    let distance = |[r2, g2, b2]: [u8; 3]| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, r2) + d(g, g2) + d(b, b2)
+    };
+
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = [LEVELS[ri], LEVELS[gi], LEVELS[bi]];
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_index = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray_level = 8 + 10 * gray_index;
+    let gray = [gray_level; 3];
+
+    if distance(gray) < distance(cube) {
+        232 + gray_index
+    } else {
+        cube_index as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+    const CLEAR: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+    fn true_color(width: Option<u32>, height: Option<u32>) -> RenderOptions {
+        RenderOptions {
+            width,
+            height,
+            color: ColorMode::TrueColor,
+        }
+    }
+
+    /// 4×4 の透明な画像の中央 2×2 に、上段が赤・下段が青
+    fn sprite() -> RgbaImage {
+        let mut img = RgbaImage::from_pixel(4, 4, CLEAR);
+        img.put_pixel(1, 1, RED);
+        img.put_pixel(2, 1, RED);
+        img.put_pixel(1, 2, BLUE);
+        img.put_pixel(2, 2, BLUE);
+        img
+    }
+
+    #[test]
+    fn test_opaque_bounds() {
+        assert_eq!(
+            opaque_bounds(&sprite()),
+            Some(Bounds {
+                x: 1,
+                y: 1,
+                width: 2,
+                height: 2,
+            })
+        );
+        assert_eq!(opaque_bounds(&RgbaImage::from_pixel(3, 3, CLEAR)), None);
+    }
+
+    #[test]
+    fn test_render_trims_and_uses_half_blocks() {
+        let lines = render(&sprite(), &true_color(None, None));
+        let cell = "\x1b[0;38;2;255;0;0;48;2;0;0;255m▀";
+        assert_eq!(lines, vec![format!("{}{}\x1b[0m", cell, cell)]);
+    }
+
+    #[test]
+    fn test_render_keeps_transparent_half() {
+        // 赤1ピクセルの下が透明、その右は上が透明で下が青
+        let mut img = RgbaImage::from_pixel(2, 2, CLEAR);
+        img.put_pixel(0, 0, RED);
+        img.put_pixel(1, 1, BLUE);
+        img.put_pixel(1, 0, CLEAR);
+
+        let lines = render(&img, &true_color(None, None));
+        assert_eq!(
+            lines,
+            vec!["\x1b[0;38;2;255;0;0m▀\x1b[0;38;2;0;0;255m▄\x1b[0m".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_render_ansi256() {
+        let options = RenderOptions {
+            width: None,
+            height: None,
+            color: ColorMode::Ansi256,
+        };
+        let lines = render(&sprite(), &options);
+        let cell = "\x1b[0;38;5;196;48;5;21m▀";
+        assert_eq!(lines, vec![format!("{}{}\x1b[0m", cell, cell)]);
+    }
+
+    #[test]
+    fn test_render_size_options() {
+        let img = RgbaImage::from_pixel(10, 20, RED);
+
+        // 幅だけなら縦横比を保つ（20 ピクセル → 10 行）
+        let lines = render(&img, &true_color(Some(5), None));
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0].matches('▀').count(), 5);
+
+        // 高さだけ
+        let lines = render(&img, &true_color(None, Some(20)));
+        assert_eq!(lines.len(), 20);
+        assert_eq!(lines[0].matches('▀').count(), 20);
+
+        // 両方なら収まる方に合わせる
+        let lines = render(&img, &true_color(Some(4), Some(20)));
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].matches('▀').count(), 4);
+    }
+
+    #[test]
+    fn test_render_shrinks_large_images_by_default() {
+        let img = RgbaImage::from_pixel(256, 256, RED);
+        let lines = render(&img, &true_color(None, None));
+        assert_eq!(lines.len(), DEFAULT_MAX_HEIGHT as usize);
+        assert_eq!(lines[0].matches('▀').count(), DEFAULT_MAX_WIDTH as usize);
+    }
+
+    #[test]
+    fn test_render_region_keeps_size_across_frames() {
+        let mut small = RgbaImage::from_pixel(4, 4, CLEAR);
+        small.put_pixel(1, 1, RED);
+        let big = sprite();
+        let bounds = opaque_bounds(&small)
+            .unwrap()
+            .union(opaque_bounds(&big).unwrap());
+
+        let options = true_color(None, None);
+        assert_eq!(
+            render_region(&small, bounds, &options).len(),
+            render_region(&big, bounds, &options).len()
+        );
+    }
+
+    #[test]
+    fn test_ansi256() {
+        assert_eq!(ansi256([255, 0, 0]), 196);
+        assert_eq!(ansi256([0, 0, 0]), 16);
+        assert_eq!(ansi256([255, 255, 255]), 231);
+        // グレーは灰色の段階へ
+        assert_eq!(ansi256([128, 128, 128]), 244);
+    }
+}
@@ -175,8 +175,9 @@ fn is_vowel(c: char) -> bool {
 }
 
 /// ひらがなをカタカナへ寄せる。変換表はカタカナのみなので、
-/// フォルム名（あかつきのつばさ 等）を引けるようにするための前処理
-fn to_katakana(c: char) -> char {
+/// フォルム名（あかつきのつばさ 等）を引けるようにするための前処理。
+/// 検索クエリの正規化（search.rs）でも使う
+pub fn to_katakana(c: char) -> char {
     match c {
         'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
         _ => c,
@@ -253,6 +254,35 @@ pub fn variants(katakana: &str) -> Vec<String> {
     }
 }
 
+/// 入力ゆれを吸収した比較用キー（コマンドライン引数のローマ字検索用）。
+///
+/// skim と違い完全一致・部分文字列で引くため、ここで吸収する必要がある。
+/// 小文字化して英数字以外（括弧・空白・ハイフン等）を落とし、長音として
+/// 同じ母音の連続と ou を1つにまとめる（pikachuu / pikachu、buusutaa / busuta）。
+/// 辞書側のキーと入力の両方に同じ変換をかけて比べる。
+pub fn loose_key(romaji: &str) -> String {
+    let mut out = String::new();
+    for c in romaji
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(char::is_ascii_alphanumeric)
+    {
+        if is_vowel(c)
+            && let Some(prev) = out.chars().last()
+            && (prev == c || (prev == 'o' && c == 'u'))
+        {
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// ローマ字入力とみなせるか。ASCII だけで、英字を1文字以上含む
+pub fn is_romaji_input(query: &str) -> bool {
+    query.is_ascii() && query.chars().any(|c| c.is_ascii_alphabetic())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,6 +369,29 @@ mod tests {
         assert_eq!(variants("サンダー"), vec!["sandaa"]);
     }
 
+    #[test]
+    fn test_loose_key_collapses_long_vowels() {
+        assert_eq!(loose_key("pikachuu"), "pikachu");
+        assert_eq!(loose_key("Pikachu"), "pikachu");
+        assert_eq!(loose_key("buusutaa"), "busuta");
+        // ou も長音として扱う
+        assert_eq!(loose_key("fushigisou"), "fushigiso");
+    }
+
+    #[test]
+    fn test_loose_key_drops_non_alphanumerics() {
+        assert_eq!(loose_key("rokon（arooranosugata）"), "rokonaroranosugata");
+        assert_eq!(loose_key("porigon-2"), "porigon2");
+    }
+
+    #[test]
+    fn test_is_romaji_input() {
+        assert!(is_romaji_input("pikachu"));
+        assert!(is_romaji_input("porigon2"));
+        assert!(!is_romaji_input("ピカチュウ"));
+        assert!(!is_romaji_input("123"));
+    }
+
     #[test]
     fn test_chouon_at_head_is_ignored() {
         // 直前に母音がない ー は伸ばす対象がないので無視する
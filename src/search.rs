@@ -1,14 +1,109 @@
 use crate::data::DataLoader;
+use crate::language::{JaScript, Language};
+use crate::models::{FORM_ID_OFFSET, NameDictionary, NameEntry};
 use anyhow::{Context, Result};
+use clap::ValueEnum;
 use std::collections::HashMap;
+use unicode_normalization::UnicodeNormalization;
+
+/// 検索用にクエリと辞書キーの表記ゆれを揃える。
+///
+/// NFKC で半角カナを全角に（濁点・半濁点も合成される）、全角英数を半角に寄せ、
+/// ひらがなをカタカナに、英字を小文字にする。表示用の名前は変えず、
+/// 比較にだけ使う。
+pub fn normalize(text: &str) -> String {
+    text.nfkc()
+        .map(crate::romaji::to_katakana)
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 日本語以外の検索キー。normalize に加えてアクセント記号を落とす
+/// （Évoli を evoli で引けるように）。日本語では濁点まで落ちるので使わない
+pub fn fold_diacritics(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
+        .nfc()
+        .collect()
+}
+
+/// 「もしかして」候補に出す類似度の下限
+pub const SUGGEST_MIN_SCORE: f64 = 0.6;
+
+/// `--best` で自動的に採用する類似度の下限
+pub const BEST_MIN_SCORE: f64 = 0.8;
+
+/// 綴り違いの候補。score は 0.0〜1.0 の類似度（1.0 が一致）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion<'a> {
+    pub ja: &'a str,
+    pub en: &'a str,
+    pub score: f64,
+}
+
+/// 部分一致の当たり方。並び順に使う（完全一致 → 前方一致 → 部分一致）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchRank {
+    Exact,
+    Prefix,
+    Substring,
+}
+
+impl MatchRank {
+    fn of(haystack: &str, needle: &str) -> Option<Self> {
+        if haystack == needle {
+            Some(MatchRank::Exact)
+        } else if haystack.starts_with(needle) {
+            Some(MatchRank::Prefix)
+        } else if haystack.contains(needle) {
+            Some(MatchRank::Substring)
+        } else {
+            None
+        }
+    }
+}
+
+/// フォルムの扱い（--forms）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum FormFilter {
+    /// フォルムも種と並べて候補に出す（既定）
+    #[default]
+    All,
+    /// 候補はフォルムを元の種にまとめて出す。フォルム名で引いても種が候補に出る
+    Collapse,
+    /// 種だけ。フォルムは検索・候補・一覧のどれにも出さない
+    Species,
+}
+
+/// 図鑑順の並びキー（種の id, フォルムか, 自身の id）。
+/// フォルムは元の種の直後に来る。id の無いエントリは最後
+type DexOrder = (u32, bool, u32);
 
 /// 検索サービス
 #[derive(Clone)]
 pub struct SearchService {
     /// 検索用HashMap（日本語名 -> 英名）
     name_map: HashMap<String, String>,
+    /// 逆引き用HashMap（英名の小文字 -> 日本語名）
+    reverse_map: HashMap<String, String>,
     /// 日本語名 -> タイプの英語スラッグ配列（タイプトークン生成用）
     type_map: HashMap<String, Vec<String>>,
+    /// 日本語名 -> エントリ全体（id・タイプ込みの構造化出力用）
+    entry_map: HashMap<String, NameEntry>,
+    /// 検索元の言語
+    language: Language,
+    /// 日本語名を表示するときの表記
+    ja_script: JaScript,
+    /// 正規化した検索元の言語の名前 -> 日本語名（ひらがな・半角カナ入力用）。
+    /// 日本語なら漢字まじりの表記も入る
+    normalized_map: HashMap<String, Vec<String>>,
+    /// ローマ字の比較用キー -> 日本語名（コマンドライン引数のローマ字検索用）。
+    /// 長音を落とすと別名が同じキーになりうるので複数持つ。検索元が日本語のときだけ作る
+    romaji_map: HashMap<String, Vec<String>>,
+    /// 日本語名 -> 図鑑順の並びキー（候補を毎回同じ順で出すため）
+    order_map: HashMap<String, DexOrder>,
+    /// フォルムの扱い
+    form_filter: FormFilter,
 }
 
 impl SearchService {
@@ -18,28 +113,239 @@ impl SearchService {
             .load_dictionary()
             .context("Failed to load dictionary")?;
 
+        Ok(Self::from_dictionary(&dictionary))
+    }
+
+    /// 読み込み済みの辞書から作成
+    pub fn from_dictionary(dictionary: &NameDictionary) -> Self {
         let name_map = dictionary.to_hashmap();
+        let reverse_map = dictionary.to_reverse_hashmap();
         let type_map = dictionary.to_type_map();
+        let entry_map = dictionary.to_entry_map();
+        let normalized_map = build_normalized_map(&entry_map);
+        let romaji_map = build_romaji_map(name_map.keys());
+        let order_map = build_order_map(&entry_map);
 
-        Ok(Self { name_map, type_map })
+        Self {
+            name_map,
+            reverse_map,
+            type_map,
+            entry_map,
+            language: Language::Ja,
+            ja_script: JaScript::Kana,
+            normalized_map,
+            romaji_map,
+            order_map,
+            form_filter: FormFilter::All,
+        }
     }
 
     /// HashMapから直接検索サービスを作成（テスト用）
     #[allow(dead_code)]
     pub fn from_name_map(name_map: HashMap<String, String>) -> Self {
-        Self {
-            name_map,
-            type_map: HashMap::new(),
-        }
+        Self::from_maps(name_map, HashMap::new())
     }
 
-    /// name_map と type_map を直接渡して作成（テスト用）
-    #[cfg(test)]
+    /// name_map と type_map を直接渡して作成（テスト用）。
+    /// 逆引きとエントリは name_map から作る（id は持たない）
+    #[allow(dead_code)]
     pub fn from_maps(
         name_map: HashMap<String, String>,
         type_map: HashMap<String, Vec<String>>,
     ) -> Self {
-        Self { name_map, type_map }
+        let reverse_map = name_map
+            .iter()
+            .map(|(ja, en)| (en.to_lowercase(), ja.clone()))
+            .collect();
+        let entry_map = name_map
+            .iter()
+            .map(|(ja, en)| {
+                let entry = NameEntry {
+                    ja: ja.clone(),
+                    en: en.clone(),
+                    id: None,
+                    types: type_map.get(ja).cloned().unwrap_or_default(),
+                    ..Default::default()
+                };
+                (ja.clone(), entry)
+            })
+            .collect();
+        let normalized_map = build_normalized_map(&entry_map);
+        let romaji_map = build_romaji_map(name_map.keys());
+        let order_map = build_order_map(&entry_map);
+        Self {
+            name_map,
+            reverse_map,
+            type_map,
+            entry_map,
+            language: Language::Ja,
+            ja_script: JaScript::Kana,
+            normalized_map,
+            romaji_map,
+            order_map,
+            form_filter: FormFilter::All,
+        }
+    }
+
+    /// 検索元の言語を切り替えて索引を作り直す（既定は日本語）。
+    /// 日本語以外ではその言語の名前だけを索引し、ローマ字の索引は作らない
+    pub fn source_language(mut self, language: Language) -> Self {
+        self.language = language;
+        if language == Language::Ja {
+            self.normalized_map = build_normalized_map(&self.entry_map);
+            self.romaji_map = build_romaji_map(self.name_map.keys());
+            return self;
+        }
+
+        let mut normalized_map: HashMap<String, Vec<String>> = HashMap::new();
+        for (ja, entry) in &self.entry_map {
+            if let Some(name) = entry.name(language) {
+                normalized_map
+                    .entry(fold_diacritics(&normalize(name)))
+                    .or_default()
+                    .push(ja.clone());
+            }
+        }
+        self.normalized_map = normalized_map;
+        self.romaji_map = HashMap::new();
+        self
+    }
+
+    /// フォルムの扱いを切り替える（既定は All）。
+    /// Species ではフォルムのエントリを捨てて索引を作り直すので、完全一致でも引けなくなる
+    pub fn form_filter(mut self, filter: FormFilter) -> Self {
+        self.form_filter = filter;
+        if filter != FormFilter::Species {
+            return self;
+        }
+
+        let forms: Vec<String> = self
+            .entry_map
+            .values()
+            .filter(|entry| entry.is_form())
+            .map(|entry| entry.ja.clone())
+            .collect();
+        for ja in &forms {
+            if let Some(entry) = self.entry_map.remove(ja) {
+                self.reverse_map.remove(&entry.en.to_lowercase());
+            }
+            self.name_map.remove(ja);
+            self.type_map.remove(ja);
+            self.order_map.remove(ja);
+        }
+        let language = self.language;
+        self.source_language(language)
+    }
+
+    /// 候補でフォルムを種にまとめるか（対話選択で種を選んだあとにフォルムを選ぶ）
+    pub fn collapses_forms(&self) -> bool {
+        self.form_filter == FormFilter::Collapse
+    }
+
+    /// 日本語名のエントリがフォルムか
+    pub fn is_form(&self, japanese_name: &str) -> bool {
+        self.entry(japanese_name).is_some_and(NameEntry::is_form)
+    }
+
+    /// 種とそのフォルムを図鑑順で返す（--forms collapse で種を選んだあとの選択肢）。
+    /// フォルムを持たない種や、フォルム自身を渡したときは空
+    pub fn forms_of(&self, japanese_name: &str) -> Vec<(&str, &str)> {
+        if self.is_form(japanese_name) {
+            return Vec::new();
+        }
+        let Some(species_id) = self.species_id(japanese_name) else {
+            return Vec::new();
+        };
+        let mut entries: Vec<(&str, &str)> = self
+            .name_map
+            .iter()
+            .filter(|(ja, _)| self.species_id(ja) == Some(species_id))
+            .map(|(ja, en)| (ja.as_str(), en.as_str()))
+            .collect();
+        if entries.len() < 2 {
+            return Vec::new();
+        }
+        entries.sort_by(|(ja_a, _), (ja_b, _)| {
+            self.dex_order(ja_a)
+                .cmp(&self.dex_order(ja_b))
+                .then_with(|| ja_a.cmp(ja_b))
+        });
+        entries
+    }
+
+    /// Collapse のとき、フォルムの日本語名を元の種の日本語名に置き換える。
+    /// 同じ種に寄ったものは `better` で1つにまとめる。元の種が分からないフォルムはそのまま
+    fn collapse_forms<'a, V: Copy>(
+        &'a self,
+        values: HashMap<&'a str, V>,
+        better: impl Fn(V, V) -> V,
+    ) -> HashMap<&'a str, V> {
+        if self.form_filter != FormFilter::Collapse {
+            return values;
+        }
+        let species: HashMap<u32, &str> = self
+            .entry_map
+            .values()
+            .filter(|entry| !entry.is_form())
+            .filter_map(|entry| entry.id.map(|id| (id, entry.ja.as_str())))
+            .collect();
+
+        let mut collapsed: HashMap<&str, V> = HashMap::new();
+        for (ja, value) in values {
+            let key = if self.is_form(ja) {
+                self.species_id(ja)
+                    .and_then(|id| species.get(&id).copied())
+                    .unwrap_or(ja)
+            } else {
+                ja
+            };
+            collapsed
+                .entry(key)
+                .and_modify(|best| *best = better(*best, value))
+                .or_insert(value);
+        }
+        collapsed
+    }
+
+    /// 辞書がスラッグを持っているか（v3 でも古い辞書には無い）
+    pub fn has_slugs(&self) -> bool {
+        self.entry_map.values().any(|entry| entry.slug.is_some())
+    }
+
+    /// 辞書が図鑑番号を持っているか（古い辞書には無い）
+    pub fn has_ids(&self) -> bool {
+        self.entry_map.values().any(|entry| entry.id.is_some())
+    }
+
+    /// 辞書がその言語の名前を持っているか。v2 以前の辞書は日本語と英語だけ
+    pub fn has_language(&self, language: Language) -> bool {
+        self.entry_map
+            .values()
+            .any(|entry| entry.name(language).is_some())
+    }
+
+    /// 日本語名を表示するときの表記（既定はカタカナ）。検索はどちらの表記でも引ける
+    pub fn ja_script(mut self, script: JaScript) -> Self {
+        self.ja_script = script;
+        self
+    }
+
+    /// 日本語名のエントリの、指定した言語での表示名。
+    /// 日本語は ja_script の表記で、漢字まじりの表記が無ければカタカナ
+    pub fn name_in(&self, japanese_name: &str, language: Language) -> Option<&str> {
+        let entry = self.entry(japanese_name)?;
+        if language == Language::Ja && self.ja_script == JaScript::Kanji {
+            return entry.ja_kanji().or(Some(&entry.ja));
+        }
+        entry.name(language)
+    }
+
+    /// クエリを normalized_map のキーと同じ形に揃える
+    fn search_key(&self, query: &str) -> String {
+        match self.language {
+            Language::Ja => normalize(query),
+            _ => fold_diacritics(&normalize(query)),
+        }
     }
 
     /// 日本語名から skim 用のタイプトークン列を作る。
@@ -91,24 +397,228 @@ impl SearchService {
         Self::from_loader(&loader)
     }
 
-    /// 日本語名から英名を検索（完全一致）
+    /// 完全一致で引ける日本語名を返す。
+    /// 日本語名そのものを優先し、次に正規化した表記（ひらがな・半角カナ）、
+    /// 最後にローマ字（ヘボン式・訓令式、長音省略可）で引く。
+    /// 日本語以外が検索元なら、その言語の名前を正規化した表記だけで引く。
+    /// 正規化やローマ字で複数の名前に当たる場合は完全一致とはみなさない
+    fn resolve_exact(&self, query: &str) -> Option<&str> {
+        if self.language == Language::Ja
+            && let Some((ja, _)) = self.name_map.get_key_value(query)
+        {
+            return Some(ja.as_str());
+        }
+
+        let normalized = self.search_key(query);
+        if let Some(ja) = unique(self.normalized_map.get(&normalized)) {
+            return Some(ja);
+        }
+
+        if self.language != Language::Ja || !crate::romaji::is_romaji_input(&normalized) {
+            return None;
+        }
+        unique(self.romaji_map.get(&crate::romaji::loose_key(&normalized)))
+    }
+
+    /// 日本語名（またはローマ字）から英名を検索（完全一致）
     #[allow(dead_code)] // CLIインターフェースで使用予定
     pub fn search_exact(&self, japanese_name: &str) -> Option<&str> {
-        self.name_map.get(japanese_name).map(|s| s.as_str())
+        self.resolve_exact(japanese_name)
+            .and_then(|ja| self.name_map.get(ja))
+            .map(|s| s.as_str())
     }
 
-    /// 部分一致検索（前方一致、後方一致、部分一致）
+    /// 部分一致検索（前方一致、後方一致、部分一致）。正規化した表記同士で比べる。
+    /// ローマ字入力なら、ローマ字の比較用キーに含まれるものも候補にする。
+    ///
+    /// 並びは完全一致 → 前方一致 → 部分一致の順で、同じ当たり方の中は図鑑順
+    /// （フォルムは元の種の直後）
     pub fn search_partial(&self, query: &str) -> Vec<(&str, &str)> {
+        let query = self.search_key(query);
+
+        let mut ranks: HashMap<&str, MatchRank> = HashMap::new();
+
+        for (normalized, names) in &self.normalized_map {
+            if let Some(rank) = MatchRank::of(normalized, &query) {
+                record_rank(&mut ranks, names, rank);
+            }
+        }
+
+        if self.language == Language::Ja && crate::romaji::is_romaji_input(&query) {
+            let key = crate::romaji::loose_key(&query);
+            for (romaji, names) in &self.romaji_map {
+                if let Some(rank) = MatchRank::of(romaji, &key) {
+                    record_rank(&mut ranks, names, rank);
+                }
+            }
+        }
+
+        self.ranked(ranks)
+    }
+
+    /// 綴り違いを許した候補を類似度の高い順に最大 `limit` 件返す。
+    ///
+    /// 類似度は編集距離を長い方の文字数で割って 1 から引いたもの。
+    /// カタカナ表記同士と、ローマ字の比較用キー同士の両方で測り、高い方を採る
+    /// （ローマ字で比べると ヒトカケ / ヒトカゲ のような濁点の打ち漏れに甘くなる）。
+    /// 日本語以外が検索元なら、その言語の名前同士だけで測る。
+    /// SUGGEST_MIN_SCORE 未満は返さない。同点は日本語名の順
+    pub fn suggest(&self, query: &str, limit: usize) -> Vec<Suggestion<'_>> {
+        let normalized = self.search_key(query);
+        if normalized.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scores: HashMap<&str, f64> = HashMap::new();
+        let mut record = |ja: &str, score: f64| {
+            if let Some((ja, _)) = self.name_map.get_key_value(ja) {
+                let best = scores.entry(ja.as_str()).or_insert(0.0);
+                *best = best.max(score);
+            }
+        };
+
+        let romaji_input =
+            self.language == Language::Ja && crate::romaji::is_romaji_input(&normalized);
+        let romaji_keys: Vec<String> = if romaji_input {
+            vec![crate::romaji::loose_key(&normalized)]
+        } else {
+            for (key, names) in &self.normalized_map {
+                let score = similarity(&normalized, key);
+                for ja in names {
+                    record(ja, score);
+                }
+            }
+            crate::romaji::variants(&normalized)
+                .iter()
+                .map(|v| crate::romaji::loose_key(v))
+                .filter(|k| !k.is_empty())
+                .collect()
+        };
+
+        for (key, names) in &self.romaji_map {
+            let score = romaji_keys
+                .iter()
+                .map(|q| similarity(q, key))
+                .fold(0.0, f64::max);
+            for ja in names {
+                record(ja, score);
+            }
+        }
+
+        let scores = self.collapse_forms(scores, f64::max);
+        let mut suggestions: Vec<Suggestion<'_>> = scores
+            .into_iter()
+            .filter(|(_, score)| *score >= SUGGEST_MIN_SCORE)
+            .filter_map(|(ja, score)| {
+                self.name_map.get(ja).map(|en| Suggestion {
+                    ja,
+                    en: en.as_str(),
+                    score,
+                })
+            })
+            .collect();
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.ja.cmp(b.ja)));
+        suggestions.truncate(limit);
+        suggestions
+    }
+
+    /// 完全一致、なければ綴り違いの最上位候補を返す（--best 用）。
+    /// 最上位が BEST_MIN_SCORE 以上で、かつ2位と同点でないときだけ採用する。
+    /// 同点だとどちらを意図したか決められないため
+    pub fn best_match(&self, query: &str) -> Option<&NameEntry> {
+        if let Some(entry) = self.entry_exact(query) {
+            return Some(entry);
+        }
+
+        let suggestions = self.suggest(query, 2);
+        let top = suggestions.first()?;
+        let ambiguous = suggestions.get(1).is_some_and(|s| s.score >= top.score);
+        if top.score < BEST_MIN_SCORE || ambiguous {
+            return None;
+        }
+        self.entry(top.ja)
+    }
+
+    /// 日本語名からエントリ全体を取得（完全一致）
+    pub fn entry(&self, japanese_name: &str) -> Option<&NameEntry> {
+        self.entry_map.get(japanese_name)
+    }
+
+    /// 日本語名（またはローマ字）からエントリ全体を取得（完全一致）。
+    /// search_exact と同じ規則で引く
+    pub fn entry_exact(&self, query: &str) -> Option<&NameEntry> {
+        self.resolve_exact(query).and_then(|ja| self.entry(ja))
+    }
+
+    /// 英名からエントリ全体を取得（完全一致、大文字小文字は区別しない）
+    pub fn entry_by_en(&self, english_name: &str) -> Option<&NameEntry> {
+        self.search_exact_en(english_name)
+            .and_then(|ja| self.entry(ja))
+    }
+
+    /// id からエントリ全体を取得（進化の系統など、番号しか分からないとき用）
+    #[cfg_attr(not(feature = "sprites"), allow(dead_code))]
+    pub fn entry_by_id(&self, id: u32) -> Option<&NameEntry> {
+        self.entry_map.values().find(|entry| entry.id == Some(id))
+    }
+
+    /// 英名から日本語名を検索（完全一致、大文字小文字は区別しない）
+    pub fn search_exact_en(&self, english_name: &str) -> Option<&str> {
+        self.reverse_map
+            .get(&english_name.to_lowercase())
+            .map(|s| s.as_str())
+    }
+
+    /// 英名の部分一致検索（大文字小文字は区別しない）。戻り値は (日本語名, 英名)。
+    /// 並びは search_partial と同じ
+    pub fn search_partial_en(&self, query: &str) -> Vec<(&str, &str)> {
         let query_lower = query.to_lowercase();
 
-        self.name_map
+        let ranks: HashMap<&str, MatchRank> = self
+            .name_map
             .iter()
-            .filter(|(ja, _)| {
-                let ja_lower = ja.to_lowercase();
-                ja_lower.contains(&query_lower)
+            .filter_map(|(ja, en)| {
+                MatchRank::of(&en.to_lowercase(), &query_lower).map(|rank| (ja.as_str(), rank))
             })
-            .map(|(ja, en)| (ja.as_str(), en.as_str()))
-            .collect()
+            .collect();
+
+        self.ranked(ranks)
+    }
+
+    /// 当たり方 → 図鑑順 → 日本語名の順に並べて (日本語名, 英名) にする。
+    /// Collapse ならフォルムは元の種に寄せる
+    fn ranked<'a>(&'a self, ranks: HashMap<&'a str, MatchRank>) -> Vec<(&'a str, &'a str)> {
+        let ranks = self.collapse_forms(ranks, MatchRank::min);
+        let mut matches: Vec<(MatchRank, &str, &str)> = ranks
+            .into_iter()
+            .filter_map(|(ja, rank)| {
+                self.name_map
+                    .get_key_value(ja)
+                    .map(|(ja, en)| (rank, ja.as_str(), en.as_str()))
+            })
+            .collect();
+        matches.sort_by(|(rank_a, ja_a, _), (rank_b, ja_b, _)| {
+            rank_a
+                .cmp(rank_b)
+                .then_with(|| self.dex_order(ja_a).cmp(&self.dex_order(ja_b)))
+                .then_with(|| ja_a.cmp(ja_b))
+        });
+        matches.into_iter().map(|(_, ja, en)| (ja, en)).collect()
+    }
+
+    /// 図鑑番号。フォルムは元の種の番号（元の種が分からなければ None）
+    pub fn species_id(&self, japanese_name: &str) -> Option<u32> {
+        self.order_map
+            .get(japanese_name)
+            .map(|(species_id, _, _)| *species_id)
+            .filter(|id| *id <= FORM_ID_OFFSET)
+    }
+
+    fn dex_order(&self, japanese_name: &str) -> DexOrder {
+        self.order_map
+            .get(japanese_name)
+            .copied()
+            .unwrap_or((u32::MAX, false, u32::MAX))
     }
 
     /// 検索可能な全エントリ数を取得
@@ -117,19 +627,134 @@ impl SearchService {
         self.name_map.len()
     }
 
-    /// 全てのエントリを取得（インタラクティブ選択用）
+    /// 全てのエントリを図鑑順で取得（インタラクティブ選択用）。フォルムは元の種の直後。
+    /// Collapse ならフォルムは出さない（種を選んでから選ぶ）
     pub fn all_entries(&self) -> Vec<(&str, &str)> {
-        self.name_map
+        let mut entries: Vec<(&str, &str)> = self
+            .name_map
             .iter()
+            .filter(|(ja, _)| self.form_filter != FormFilter::Collapse || !self.is_form(ja))
             .map(|(ja, en)| (ja.as_str(), en.as_str()))
-            .collect()
+            .collect();
+        entries.sort_by(|(ja_a, _), (ja_b, _)| {
+            self.dex_order(ja_a)
+                .cmp(&self.dex_order(ja_b))
+                .then_with(|| ja_a.cmp(ja_b))
+        });
+        entries
+    }
+}
+
+/// 文字単位の編集距離（挿入・削除・置換がそれぞれ 1）
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// 編集距離を長い方の文字数で割った類似度（0.0〜1.0）
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let len = a.len().max(b.len());
+    if len == 0 {
+        return 0.0;
+    }
+    1.0 - edit_distance(&a, &b) as f64 / len as f64
+}
+
+/// 複数の経路（カタカナ・ローマ字）で当たった名前は、最も良い当たり方を残す
+fn record_rank<'a>(ranks: &mut HashMap<&'a str, MatchRank>, names: &'a [String], rank: MatchRank) {
+    for ja in names {
+        let best = ranks.entry(ja.as_str()).or_insert(rank);
+        *best = (*best).min(rank);
+    }
+}
+
+/// 候補がちょうど1件のときだけ返す
+fn unique(names: Option<&Vec<String>>) -> Option<&str> {
+    match names.map(Vec::as_slice) {
+        Some([ja]) => Some(ja.as_str()),
+        _ => None,
+    }
+}
+
+/// 正規化した日本語名から元の日本語名を引く索引を作る。
+/// 漢字まじりの表記（ロコン（アローラの姿））も同じ日本語名に向ける
+fn build_normalized_map(entry_map: &HashMap<String, NameEntry>) -> HashMap<String, Vec<String>> {
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    for (ja, entry) in entry_map {
+        for name in std::iter::once(ja.as_str()).chain(entry.ja_kanji()) {
+            let names = map.entry(normalize(name)).or_default();
+            if !names.contains(ja) {
+                names.push(ja.clone());
+            }
+        }
+    }
+    map
+}
+
+/// 日本語名ごとに図鑑順の並びキーを作る。
+///
+/// フォルムの元の種は v3 の辞書なら species_id で分かる。それより前の辞書は
+/// 持たないので、フォルム名に含まれる種名のうち最も長いもの
+/// （メガミュウツーX なら ミュウ ではなく ミュウツー）を元の種とみなす。
+/// 見つからないフォルムは種の後ろにまとめる
+fn build_order_map(entry_map: &HashMap<String, NameEntry>) -> HashMap<String, DexOrder> {
+    let species: Vec<(&str, u32)> = entry_map
+        .values()
+        .filter(|e| !e.is_form())
+        .filter_map(|e| e.id.map(|id| (e.ja.as_str(), id)))
+        .collect();
+
+    entry_map
+        .iter()
+        .filter_map(|(ja, entry)| {
+            let id = entry.id?;
+            if !entry.is_form() {
+                return Some((ja.clone(), (id, false, id)));
+            }
+            let species_id = entry.species_id.unwrap_or_else(|| {
+                species
+                    .iter()
+                    .filter(|(species_ja, _)| ja.contains(species_ja))
+                    .max_by_key(|(species_ja, _)| species_ja.chars().count())
+                    .map_or(id, |(_, species_id)| *species_id)
+            });
+            Some((ja.clone(), (species_id, true, id)))
+        })
+        .collect()
+}
+
+/// 日本語名ごとにローマ字表記（ヘボン式・訓令式）を比較用キーにして索引を作る
+fn build_romaji_map<'a>(names: impl Iterator<Item = &'a String>) -> HashMap<String, Vec<String>> {
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    for ja in names {
+        for variant in crate::romaji::variants(ja) {
+            let key = crate::romaji::loose_key(&variant);
+            if key.is_empty() {
+                continue;
+            }
+            let names = map.entry(key).or_default();
+            if !names.contains(ja) {
+                names.push(ja.clone());
+            }
+        }
     }
+    map
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{NameDictionary, NameEntry};
     use chrono::Utc;
     use std::fs;
     use tempfile::tempdir;
@@ -142,10 +767,7 @@ mod tests {
         name_map.insert("フシギバナ".to_string(), "Venusaur".to_string());
         name_map.insert("ヒトカゲ".to_string(), "Charmander".to_string());
 
-        SearchService {
-            name_map,
-            type_map: HashMap::new(),
-        }
+        SearchService::from_name_map(name_map)
     }
 
     #[test]
@@ -210,12 +832,493 @@ mod tests {
         assert_eq!(service.search_exact("ピカ"), None); // 部分一致はしない
     }
 
+    #[test]
+    fn test_normalize() {
+        // ひらがな → カタカナ
+        assert_eq!(normalize("ぴかちゅう"), "ピカチュウ");
+        // 半角カナ → 全角（濁点・半濁点は合成される）
+        assert_eq!(normalize("ﾋﾟｶﾁｭｳ"), "ピカチュウ");
+        assert_eq!(normalize("ﾌｼｷﾞﾀﾞﾈ"), "フシギダネ");
+        assert_eq!(normalize("ﾌﾞｰｽﾀｰ"), "ブースター");
+        // 全角英数は半角・小文字に
+        assert_eq!(normalize("ポリゴン２"), "ポリゴン2");
+        assert_eq!(normalize("ＰＩＫＡ"), "pika");
+    }
+
+    #[test]
+    fn test_search_exact_normalizes_hiragana_and_halfwidth() {
+        let service = create_test_service();
+        assert_eq!(service.search_exact("ぴかちゅう"), Some("Pikachu"));
+        assert_eq!(service.search_exact("ﾋﾟｶﾁｭｳ"), Some("Pikachu"));
+        assert_eq!(service.search_exact("ふしぎだね"), Some("Bulbasaur"));
+        // 全角ローマ字も正規化してからローマ字として引く
+        assert_eq!(service.search_exact("ｐｉｋａｃｈｕ"), Some("Pikachu"));
+    }
+
+    #[test]
+    fn test_search_partial_normalizes_query() {
+        let service = create_test_service();
+        assert_eq!(service.search_partial("ﾌｼｷﾞ").len(), 3);
+        assert_eq!(
+            service.search_partial("ふしぎば"),
+            vec![("フシギバナ", "Venusaur")]
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(edit_distance(&chars("フシギダナ"), &chars("フシギダネ")), 1);
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(edit_distance(&chars(""), &chars("abc")), 3);
+        assert_eq!(similarity("ピカチュウ", "ピカチュウ"), 1.0);
+    }
+
+    #[test]
+    fn test_suggest_ranks_typo_candidates() {
+        let service = create_test_service();
+        assert!(service.search_partial("フシギダナ").is_empty());
+
+        let suggestions = service.suggest("フシギダナ", 5);
+        let names: Vec<&str> = suggestions.iter().map(|s| s.ja).collect();
+        // 1文字違いの2件が同点で先頭、同点は日本語名の順
+        assert_eq!(&names[..2], ["フシギダネ", "フシギバナ"]);
+        assert_eq!(suggestions[0].score, suggestions[1].score);
+        assert!(suggestions.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(!names.contains(&"ピカチュウ"));
+    }
+
+    #[test]
+    fn test_suggest_romaji_typo() {
+        let service = create_test_service();
+        let suggestions = service.suggest("pikachyu", 3);
+        assert_eq!(suggestions[0].ja, "ピカチュウ");
+        assert_eq!(suggestions[0].en, "Pikachu");
+    }
+
+    #[test]
+    fn test_suggest_nothing_similar() {
+        let service = create_test_service();
+        assert!(service.suggest("ミュウツー", 5).is_empty());
+        assert!(service.suggest("", 5).is_empty());
+    }
+
+    #[test]
+    fn test_best_match() {
+        let service = create_test_service();
+        // 完全一致はそのまま
+        assert_eq!(service.best_match("ピカチュウ").unwrap().en, "Pikachu");
+        // 濁点の打ち漏れはローマ字で比べると近い
+        assert_eq!(service.best_match("ヒトカケ").unwrap().en, "Charmander");
+        assert_eq!(service.best_match("ぴかちゅー").unwrap().en, "Pikachu");
+        // 同点の候補が複数あるときは選ばない
+        assert_eq!(service.best_match("フシギダナ"), None);
+        // 似ていないものは選ばない
+        assert_eq!(service.best_match("ミュウツー"), None);
+    }
+
+    #[test]
+    fn test_search_exact_accepts_romaji() {
+        let service = create_test_service();
+        // ヘボン式・訓令式・長音省略のいずれでも引ける
+        assert_eq!(service.search_exact("pikachuu"), Some("Pikachu"));
+        assert_eq!(service.search_exact("pikachu"), Some("Pikachu"));
+        assert_eq!(service.search_exact("pikatyuu"), Some("Pikachu"));
+        assert_eq!(service.search_exact("HUSIGIDANE"), Some("Bulbasaur"));
+        assert_eq!(service.search_exact("hitokage"), Some("Charmander"));
+        // 部分一致は完全一致扱いしない
+        assert_eq!(service.search_exact("pika"), None);
+    }
+
+    #[test]
+    fn test_search_exact_romaji_ambiguous_is_not_exact() {
+        let mut name_map = HashMap::new();
+        // 長音を落とすと同じキー（busuta）になる
+        name_map.insert("ブースター".to_string(), "Flareon".to_string());
+        name_map.insert("ブスタ".to_string(), "Dummy".to_string());
+        let service = SearchService::from_name_map(name_map);
+
+        assert_eq!(service.search_exact("busuta"), None);
+        assert_eq!(service.search_partial("busuta").len(), 2);
+    }
+
+    #[test]
+    fn test_search_partial_accepts_romaji() {
+        let service = create_test_service();
+        let mut matches = service.search_partial("fushigi");
+        matches.sort();
+        assert_eq!(
+            matches,
+            vec![
+                ("フシギソウ", "Ivysaur"),
+                ("フシギダネ", "Bulbasaur"),
+                ("フシギバナ", "Venusaur"),
+            ]
+        );
+        assert_eq!(
+            service.search_partial("husigida"),
+            vec![("フシギダネ", "Bulbasaur")]
+        );
+    }
+
+    #[test]
+    fn test_entry_exact_accepts_romaji() {
+        let service = create_test_service();
+        assert_eq!(service.entry_exact("pikachu").unwrap().ja, "ピカチュウ");
+        assert_eq!(service.entry_exact("ピカチュウ").unwrap().en, "Pikachu");
+        assert!(service.entry_exact("mewtwo").is_none());
+    }
+
+    #[test]
+    fn test_search_exact_en_is_case_insensitive() {
+        let service = create_test_service();
+        assert_eq!(service.search_exact_en("Pikachu"), Some("ピカチュウ"));
+        assert_eq!(service.search_exact_en("pikachu"), Some("ピカチュウ"));
+        assert_eq!(service.search_exact_en("BULBASAUR"), Some("フシギダネ"));
+        assert_eq!(service.search_exact_en("Pika"), None); // 部分一致はしない
+    }
+
+    #[test]
+    fn test_search_partial_en() {
+        let service = create_test_service();
+        let mut matches = service.search_partial_en("SAUR");
+        matches.sort();
+        assert_eq!(
+            matches,
+            vec![
+                ("フシギソウ", "Ivysaur"),
+                ("フシギダネ", "Bulbasaur"),
+                ("フシギバナ", "Venusaur"),
+            ]
+        );
+        assert!(service.search_partial_en("mewtwo").is_empty());
+    }
+
+    #[test]
+    fn test_entry_lookup() {
+        let mut name_map = HashMap::new();
+        name_map.insert("リザードン".to_string(), "Charizard".to_string());
+        let mut type_map = HashMap::new();
+        type_map.insert(
+            "リザードン".to_string(),
+            vec!["fire".to_string(), "flying".to_string()],
+        );
+        let service = SearchService::from_maps(name_map, type_map);
+
+        let entry = service.entry("リザードン").unwrap();
+        assert_eq!(entry.en, "Charizard");
+        assert_eq!(entry.types, vec!["fire", "flying"]);
+        assert_eq!(service.entry_by_en("charizard"), Some(entry));
+        assert!(service.entry("ピカチュウ").is_none());
+    }
+
     #[test]
     fn test_entry_count() {
         let service = create_test_service();
         assert_eq!(service.entry_count(), 5);
     }
 
+    fn entry(ja: &str, en: &str, id: u32) -> NameEntry {
+        NameEntry {
+            ja: ja.to_string(),
+            en: en.to_string(),
+            id: Some(id),
+            types: vec![],
+            ..Default::default()
+        }
+    }
+
+    /// id 付きの辞書。フォルム（id > 10000）と、名前が包含関係にある種を含む
+    fn create_dex_service() -> SearchService {
+        let entries = vec![
+            entry("メガミュウツーX", "Mewtwo Mega X", 10043),
+            entry("ミュウツー", "Mewtwo", 150),
+            entry("ミュウ", "Mew", 151),
+            entry("ロコン（アローラのすがた）", "Alolan Vulpix", 10103),
+            entry("ロコン", "Vulpix", 37),
+            entry("キュウコン", "Ninetales", 38),
+        ];
+        SearchService::from_dictionary(&NameDictionary {
+            schema_version: 2,
+            generated_at: Utc::now(),
+            count: entries.len(),
+            entries,
+        })
+    }
+
+    /// フランス語・韓国語の名前を持つ v3 の辞書
+    fn create_multilingual_service() -> SearchService {
+        let names = [
+            ("イーブイ", "Eevee", 133, "Évoli", "이브이"),
+            ("エーフィ", "Espeon", 196, "Mentali", "에브이"),
+            ("ブースター", "Flareon", 136, "Pyroli", "부스터"),
+        ];
+        let entries: Vec<NameEntry> = names
+            .iter()
+            .map(|&(ja, en, id, fr, ko)| {
+                let mut e = entry(ja, en, id);
+                e.names.insert("fr".to_string(), fr.to_string());
+                e.names.insert("ko".to_string(), ko.to_string());
+                e
+            })
+            .collect();
+        SearchService::from_dictionary(&NameDictionary {
+            schema_version: 3,
+            generated_at: Utc::now(),
+            count: entries.len(),
+            entries,
+        })
+    }
+
+    #[test]
+    fn test_source_language_indexes_that_language() {
+        let service = create_multilingual_service().source_language(Language::Fr);
+
+        // アクセント記号と大文字小文字は問わない
+        assert_eq!(service.search_exact("evoli"), Some("Eevee"));
+        assert_eq!(service.search_exact("Évoli"), Some("Eevee"));
+        // 検索元以外の言語の名前では引かない
+        assert_eq!(service.search_exact("イーブイ"), None);
+        assert_eq!(service.search_exact("Eevee"), None);
+
+        let partial: Vec<&str> = service
+            .search_partial("oli")
+            .iter()
+            .map(|(ja, _)| *ja)
+            .collect();
+        assert_eq!(partial, ["イーブイ", "ブースター"]);
+
+        // 綴り違いもその言語の名前同士で測る（romaji 入力扱いにしない）
+        assert_eq!(service.suggest("pyrolli", 1)[0].ja, "ブースター");
+    }
+
+    #[test]
+    fn test_source_language_korean_and_back_to_japanese() {
+        let service = create_multilingual_service().source_language(Language::Ko);
+        assert_eq!(service.search_exact("부스터"), Some("Flareon"));
+        assert_eq!(service.name_in("ブースター", Language::Fr), Some("Pyroli"));
+
+        // 日本語に戻せばローマ字も含めて従来どおり
+        let service = service.source_language(Language::Ja);
+        assert_eq!(service.search_exact("ibui"), Some("Eevee"));
+        assert_eq!(service.search_exact("부스터"), None);
+    }
+
+    #[test]
+    fn test_kanji_names_are_indexed_alongside_kana() {
+        let mut alola = entry("ロコン（アローラのすがた）", "Vulpix Alola", 10103);
+        alola
+            .names
+            .insert("ja".to_string(), "ロコン（アローラの姿）".to_string());
+        let mut vulpix = entry("ロコン", "Vulpix", 37);
+        vulpix.names.insert("ja".to_string(), "ロコン".to_string());
+        let entries = vec![alola, vulpix];
+        let service = SearchService::from_dictionary(&NameDictionary {
+            schema_version: 3,
+            generated_at: Utc::now(),
+            count: entries.len(),
+            entries,
+        });
+
+        // 公式サイトの表記（漢字まじり）でも、カタカナ表記でも引ける
+        assert_eq!(
+            service.search_exact("ロコン（アローラの姿）"),
+            Some("Vulpix Alola")
+        );
+        assert_eq!(
+            service.search_exact("ロコン（アローラのすがた）"),
+            Some("Vulpix Alola")
+        );
+        let partial: Vec<&str> = service
+            .search_partial("の姿")
+            .iter()
+            .map(|(_, en)| *en)
+            .collect();
+        assert_eq!(partial, ["Vulpix Alola"]);
+
+        // 表示の表記は選べる。漢字名が無ければカタカナ
+        assert_eq!(
+            service.name_in("ロコン（アローラのすがた）", Language::Ja),
+            Some("ロコン（アローラのすがた）")
+        );
+        let service = service.ja_script(JaScript::Kanji);
+        assert_eq!(
+            service.name_in("ロコン（アローラのすがた）", Language::Ja),
+            Some("ロコン（アローラの姿）")
+        );
+        assert_eq!(service.name_in("ロコン", Language::Ja), Some("ロコン"));
+        assert_eq!(service.name_in("ロコン", Language::En), Some("Vulpix"));
+    }
+
+    #[test]
+    fn test_has_ids() {
+        assert!(create_dex_service().has_ids());
+        assert!(!create_test_service().has_ids());
+    }
+
+    #[test]
+    fn test_has_language() {
+        let service = create_multilingual_service();
+        assert!(service.has_language(Language::Fr));
+        assert!(!service.has_language(Language::De));
+        // 日本語と英語は v2 以前の辞書でも持っている
+        assert!(create_dex_service().has_language(Language::En));
+        assert!(!create_dex_service().has_language(Language::Ko));
+    }
+
+    #[test]
+    fn test_all_entries_in_dex_order_with_forms_after_species() {
+        let service = create_dex_service();
+        let names: Vec<&str> = service.all_entries().iter().map(|(ja, _)| *ja).collect();
+        assert_eq!(
+            names,
+            [
+                "ロコン",
+                "ロコン（アローラのすがた）",
+                "キュウコン",
+                "ミュウツー",
+                "メガミュウツーX",
+                "ミュウ",
+            ]
+        );
+        assert_eq!(service.species_id("ロコン（アローラのすがた）"), Some(37));
+        assert_eq!(service.species_id("メガミュウツーX"), Some(150));
+        assert_eq!(service.species_id("ミュウ"), Some(151));
+    }
+
+    #[test]
+    fn test_entry_by_id() {
+        let service = create_dex_service();
+        assert_eq!(service.entry_by_id(38).unwrap().ja, "キュウコン");
+        assert_eq!(service.entry_by_id(10103).unwrap().en, "Alolan Vulpix");
+        assert!(service.entry_by_id(25).is_none());
+    }
+
+    #[test]
+    fn test_search_partial_ranks_exact_prefix_substring() {
+        let service = create_dex_service();
+        let names: Vec<&str> = service
+            .search_partial("ミュウ")
+            .iter()
+            .map(|(ja, _)| *ja)
+            .collect();
+        // 完全一致 → 前方一致 → 部分一致。同じ当たり方の中は図鑑順
+        assert_eq!(names, ["ミュウ", "ミュウツー", "メガミュウツーX"]);
+
+        let names: Vec<&str> = service
+            .search_partial("コン")
+            .iter()
+            .map(|(ja, _)| *ja)
+            .collect();
+        assert_eq!(
+            names,
+            ["ロコン", "ロコン（アローラのすがた）", "キュウコン"]
+        );
+    }
+
+    #[test]
+    fn test_search_partial_en_ranked() {
+        let service = create_dex_service();
+        let names: Vec<&str> = service
+            .search_partial_en("mew")
+            .iter()
+            .map(|(_, en)| *en)
+            .collect();
+        assert_eq!(names, ["Mew", "Mewtwo", "Mewtwo Mega X"]);
+    }
+
+    #[test]
+    fn test_collapse_merges_forms_into_species() {
+        let service = create_dex_service().form_filter(FormFilter::Collapse);
+        // フォルム名でしか当たらなくても元の種が候補に出る
+        let names: Vec<&str> = service
+            .search_partial("メガ")
+            .iter()
+            .map(|(ja, _)| *ja)
+            .collect();
+        assert_eq!(names, ["ミュウツー"]);
+
+        let names: Vec<&str> = service
+            .search_partial("コン")
+            .iter()
+            .map(|(ja, _)| *ja)
+            .collect();
+        assert_eq!(names, ["ロコン", "キュウコン"]);
+        assert!(
+            service
+                .all_entries()
+                .iter()
+                .all(|(ja, _)| !service.is_form(ja))
+        );
+
+        // フォルム名そのものなら引ける
+        assert_eq!(
+            service.search_exact("メガミュウツーX"),
+            Some("Mewtwo Mega X")
+        );
+        assert_eq!(
+            service.forms_of("ミュウツー"),
+            [
+                ("ミュウツー", "Mewtwo"),
+                ("メガミュウツーX", "Mewtwo Mega X")
+            ]
+        );
+        assert!(service.forms_of("ミュウ").is_empty());
+        assert!(service.forms_of("メガミュウツーX").is_empty());
+    }
+
+    #[test]
+    fn test_species_only_drops_forms() {
+        let service = create_dex_service().form_filter(FormFilter::Species);
+        assert_eq!(service.search_exact("メガミュウツーX"), None);
+        assert_eq!(service.search_exact_en("Alolan Vulpix"), None);
+        assert_eq!(service.entry_count(), 4);
+        let names: Vec<&str> = service
+            .search_partial("ミュウ")
+            .iter()
+            .map(|(ja, _)| *ja)
+            .collect();
+        assert_eq!(names, ["ミュウ", "ミュウツー"]);
+        assert!(
+            service
+                .suggest("メガミュウツー", 5)
+                .iter()
+                .all(|s| s.ja != "メガミュウツーX")
+        );
+    }
+
+    #[test]
+    fn test_species_id_from_dictionary_wins_over_name() {
+        // 種名を含まないフォルム名でも v3 の species_id で元の種の直後に並ぶ
+        let mut zen = entry("ダルマモード", "Darmanitan Zen", 10017);
+        zen.species_id = Some(555);
+        zen.is_default = Some(false);
+        let entries = vec![
+            zen,
+            entry("ヒヒダルマ", "Darmanitan", 555),
+            entry("ミュウ", "Mew", 151),
+        ];
+        let service = SearchService::from_dictionary(&NameDictionary {
+            schema_version: 3,
+            generated_at: Utc::now(),
+            count: entries.len(),
+            entries,
+        });
+        let names: Vec<&str> = service.all_entries().iter().map(|(ja, _)| *ja).collect();
+        assert_eq!(names, ["ミュウ", "ヒヒダルマ", "ダルマモード"]);
+        assert_eq!(service.species_id("ダルマモード"), Some(555));
+    }
+
+    #[test]
+    fn test_search_partial_is_deterministic() {
+        let service = create_dex_service();
+        let first = service.search_partial("ン");
+        for _ in 0..5 {
+            let other = create_dex_service();
+            assert_eq!(other.search_partial("ン"), first);
+        }
+    }
+
     #[test]
     fn test_all_entries() {
         let service = create_test_service();
@@ -239,12 +1342,14 @@ mod tests {
                     en: "Pikachu".to_string(),
                     id: None,
                     types: vec![],
+                    ..Default::default()
                 },
                 NameEntry {
                     ja: "フシギダネ".to_string(),
                     en: "Bulbasaur".to_string(),
                     id: None,
                     types: vec![],
+                    ..Default::default()
                 },
             ],
         };
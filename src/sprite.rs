@@ -1,6 +1,12 @@
 #[cfg(feature = "sprites")]
+use crate::render::{self, RenderOptions};
+#[cfg(feature = "sprites")]
 use anyhow::{Context, Result};
 #[cfg(feature = "sprites")]
+use clap::ValueEnum;
+#[cfg(feature = "sprites")]
+use crossterm::event::{self, Event, KeyEvent, KeyEventKind};
+#[cfg(feature = "sprites")]
 use directories::ProjectDirs;
 #[cfg(feature = "sprites")]
 use reqwest::blocking::Client;
@@ -8,6 +14,171 @@ use reqwest::blocking::Client;
 use std::collections::HashMap;
 #[cfg(feature = "sprites")]
 use std::path::{Path, PathBuf};
+#[cfg(feature = "sprites")]
+use std::time::{Duration, Instant};
+
+/// 静止画の描き方
+#[cfg(feature = "sprites")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SpriteRenderer {
+    /// 画像プロトコル（Kitty / iTerm2）が使える端末なら graphics、それ以外は blocks
+    #[default]
+    Auto,
+    /// viuer で画像プロトコルを使って描く（使えない端末では viuer 自身のブロック表示）
+    Graphics,
+    /// 半角ブロックと色で描く（render モジュール）
+    Blocks,
+}
+
+#[cfg(feature = "sprites")]
+impl SpriteRenderer {
+    /// viuer で描くか。Auto は端末の画像プロトコル対応を見て決める
+    fn uses_graphics(self) -> bool {
+        match self {
+            SpriteRenderer::Auto => {
+                viuer::is_iterm_supported()
+                    || viuer::get_kitty_support() != viuer::KittySupport::None
+            }
+            SpriteRenderer::Graphics => true,
+            SpriteRenderer::Blocks => false,
+        }
+    }
+}
+
+/// スプライトの種類（PokeAPI/sprites の sprites/pokemon/ 配下のどれを使うか）
+#[cfg(feature = "sprites")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SpriteVariant {
+    /// 通常（正面）
+    #[default]
+    Default,
+    /// 色違い
+    Shiny,
+    /// 後ろ姿
+    Back,
+    /// 後ろ姿の色違い
+    BackShiny,
+    /// 公式アートワーク（大きい）
+    OfficialArtwork,
+    /// Pokémon HOME の3D風画像（大きい）
+    Home,
+    /// メスの姿（性別で見た目が違うポケモンのみ）
+    Female,
+}
+
+#[cfg(feature = "sprites")]
+impl SpriteVariant {
+    /// 確認画面で切り替える順
+    pub const ALL: [SpriteVariant; 7] = [
+        SpriteVariant::Default,
+        SpriteVariant::Shiny,
+        SpriteVariant::Back,
+        SpriteVariant::BackShiny,
+        SpriteVariant::OfficialArtwork,
+        SpriteVariant::Home,
+        SpriteVariant::Female,
+    ];
+
+    /// sprites/pokemon/ からの相対ディレクトリ
+    fn remote_dir(self) -> &'static str {
+        match self {
+            SpriteVariant::Default => "",
+            SpriteVariant::Shiny => "shiny/",
+            SpriteVariant::Back => "back/",
+            SpriteVariant::BackShiny => "back/shiny/",
+            SpriteVariant::OfficialArtwork => "other/official-artwork/",
+            SpriteVariant::Home => "other/home/",
+            SpriteVariant::Female => "female/",
+        }
+    }
+
+    /// アニメーション GIF の取得元ディレクトリからの相対パス。
+    /// 公式アートワークと HOME には動くものが無い
+    fn animated_dir(self) -> Option<&'static str> {
+        match self {
+            SpriteVariant::Default => Some(""),
+            SpriteVariant::Shiny => Some("shiny/"),
+            SpriteVariant::Back => Some("back/"),
+            SpriteVariant::BackShiny => Some("back/shiny/"),
+            SpriteVariant::Female => Some("female/"),
+            SpriteVariant::OfficialArtwork | SpriteVariant::Home => None,
+        }
+    }
+
+    /// キャッシュの名前空間（sprites/ 配下のサブディレクトリ）。
+    /// 通常は従来どおり sprites/ 直下に置く
+    fn cache_namespace(self) -> Option<&'static str> {
+        match self {
+            SpriteVariant::Default => None,
+            SpriteVariant::Shiny => Some("shiny"),
+            SpriteVariant::Back => Some("back"),
+            SpriteVariant::BackShiny => Some("back-shiny"),
+            SpriteVariant::OfficialArtwork => Some("official-artwork"),
+            SpriteVariant::Home => Some("home"),
+            SpriteVariant::Female => Some("female"),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SpriteVariant::Default => "通常",
+            SpriteVariant::Shiny => "色違い",
+            SpriteVariant::Back => "後ろ姿",
+            SpriteVariant::BackShiny => "後ろ姿（色違い）",
+            SpriteVariant::OfficialArtwork => "公式アートワーク",
+            SpriteVariant::Home => "HOME",
+            SpriteVariant::Female => "メス",
+        }
+    }
+
+    /// このバリアントが無い id で順に試すもの（先頭は自身、最後は必ず通常）。
+    /// 後ろ姿は第5世代までしか無く、メスの姿は一部のポケモンにしか無い
+    fn fallbacks(self) -> &'static [SpriteVariant] {
+        use SpriteVariant::*;
+        match self {
+            Default => &[Default],
+            Shiny => &[Shiny, Default],
+            Back => &[Back, Default],
+            BackShiny => &[BackShiny, Back, Shiny, Default],
+            OfficialArtwork => &[OfficialArtwork, Home, Default],
+            Home => &[Home, OfficialArtwork, Default],
+            Female => &[Female, Default],
+        }
+    }
+
+    /// 確認画面で次に表示するもの（最後の次は先頭）
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|v| *v == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// アニメーション GIF の取得元（sprites/pokemon/ からの相対）。上から順に試す。
+/// ブラック・ホワイトのものは第5世代まで、Showdown のものはそれ以降も揃っている
+#[cfg(feature = "sprites")]
+const ANIMATION_SOURCES: [&str; 2] = [
+    "versions/generation-v/black-white/animated/",
+    "other/showdown/",
+];
+
+/// 取得したスプライトのファイル
+#[cfg(feature = "sprites")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum SpriteFile {
+    Still(PathBuf),
+    Animated(PathBuf),
+}
+
+/// 表示したスプライト
+#[cfg(feature = "sprites")]
+pub struct DisplayedSprite {
+    /// 実際に表示した種類（代わりを表示した場合は要求と異なる）
+    pub variant: SpriteVariant,
+    /// アニメーション表示中なら、その続きを描くためのもの
+    pub animation: Option<SpriteAnimation>,
+    /// 画像に使った行数（戻って描き直すとき用）
+    pub height: u16,
+}
 
 /// スプライト画像の取得・表示を管理するサービス
 #[cfg(feature = "sprites")]
@@ -16,14 +187,16 @@ pub struct SpriteService {
     client: Client,
     base_url: String,
     id_map: HashMap<String, u32>,
+    /// 描画サイズと色
+    render_options: RenderOptions,
+    /// 静止画の描き方
+    renderer: SpriteRenderer,
 }
 
 #[cfg(feature = "sprites")]
 impl SpriteService {
-    /// 新しいSpriteServiceインスタンスを作成
-    pub fn new() -> Result<Self> {
-        use crate::data::DataLoader;
-
+    /// 新しいSpriteServiceインスタンスを作成。id は `loader` の辞書（--dict）から引く
+    pub fn with_loader(loader: &crate::data::DataLoader) -> Result<Self> {
         let project_dirs = ProjectDirs::from("", "", "poke-lookup")
             .or_else(|| ProjectDirs::from("dev", "poke-lookup", "poke-lookup"))
             .context("Failed to determine project directories")?;
@@ -45,7 +218,6 @@ impl SpriteService {
             .context("Failed to create HTTP client")?;
 
         // Load Pokemon ID mapping
-        let loader = DataLoader::new()?;
         let dictionary = loader.load_dictionary()?;
         let id_map = dictionary
             .entries
@@ -58,9 +230,22 @@ impl SpriteService {
             client,
             base_url: "https://raw.githubusercontent.com/PokeAPI/sprites/master".to_string(),
             id_map,
+            render_options: RenderOptions::default(),
+            renderer: SpriteRenderer::default(),
         })
     }
 
+    /// 描画サイズ（文字数）を指定する。色は端末から判定したものを使う
+    pub fn set_size(&mut self, width: Option<u32>, height: Option<u32>) {
+        self.render_options.width = width;
+        self.render_options.height = height;
+    }
+
+    /// 静止画の描き方を指定する。アニメーションと compare は常にブロックで描く
+    pub fn set_renderer(&mut self, renderer: SpriteRenderer) {
+        self.renderer = renderer;
+    }
+
     /// 英名からポケモンIDを取得
     pub fn get_pokemon_id(&self, english_name: &str) -> Option<u32> {
         self.id_map.get(english_name).copied()
@@ -68,37 +253,168 @@ impl SpriteService {
 
     /// ポケモンIDからローカルキャッシュのスプライトパスを取得
     pub fn get_sprite_path(&self, pokemon_id: u32) -> PathBuf {
-        self.cache_dir.join(format!("{}.png", pokemon_id))
+        self.get_variant_path(pokemon_id, SpriteVariant::Default)
     }
 
-    /// ポケモンのスプライト画像を取得して表示
-    pub fn display_sprite_for_pokemon(&self, english_name: &str) -> Result<()> {
-        if let Some(pokemon_id) = self.get_pokemon_id(english_name) {
-            match self.fetch_sprite(pokemon_id) {
-                Ok(sprite_path) => {
-                    self.display_sprite(&sprite_path)?;
-                }
-                Err(_) => {
-                    // 静かに失敗
+    /// バリアントごとのキャッシュパス。通常以外は名前空間のサブディレクトリに置く
+    pub fn get_variant_path(&self, pokemon_id: u32, variant: SpriteVariant) -> PathBuf {
+        let dir = match variant.cache_namespace() {
+            Some(namespace) => self.cache_dir.join(namespace),
+            None => self.cache_dir.clone(),
+        };
+        dir.join(format!("{}.png", pokemon_id))
+    }
+
+    /// ポケモンのスプライト画像を取得して表示する。無ければ fallbacks の順に代わりを探す。
+    /// `animate` なら各候補でまずアニメーションを試し、最初のフレームを描く。
+    /// 何も表示できなければ None
+    pub fn display_variant_for_pokemon(
+        &self,
+        english_name: &str,
+        variant: SpriteVariant,
+        animate: bool,
+    ) -> Result<Option<DisplayedSprite>> {
+        use std::io::IsTerminal;
+
+        let Some(pokemon_id) = self.get_pokemon_id(english_name) else {
+            return Ok(None);
+        };
+
+        // 描き直しはカーソル移動とキー入力待ちを伴うので、端末のときだけ
+        let animate = animate && std::io::stdout().is_terminal() && std::io::stdin().is_terminal();
+        let Ok((shown, file)) = self.fetch_with_fallback(pokemon_id, variant, animate) else {
+            // 静かに失敗
+            return Ok(None);
+        };
+        let (animation, height) = match file {
+            SpriteFile::Animated(path) => {
+                match SpriteAnimation::open(&path, &self.render_options) {
+                    Ok(mut animation) => {
+                        animation.draw_first()?;
+                        let height = animation.height;
+                        (Some(animation), height)
+                    }
+                    // 読めなければ同じ種類の静止画に代える
+                    Err(_) => match self.fetch_variant(pokemon_id, shown) {
+                        Ok(path) => (None, self.display_sprite(&path)?),
+                        Err(_) => (None, 0),
+                    },
                 }
             }
+            SpriteFile::Still(path) => (None, self.display_sprite(&path)?),
+        };
+        Ok(Some(DisplayedSprite {
+            variant: shown,
+            animation,
+            height,
+        }))
+    }
+
+    /// fallbacks の順に取得を試し、最初に取れたバリアントとファイルを返す。
+    /// `animate` なら各候補でアニメーションを先に試す（代わりの種類のアニメーションより、
+    /// 指定した種類の静止画を優先する）
+    pub fn fetch_with_fallback(
+        &self,
+        pokemon_id: u32,
+        variant: SpriteVariant,
+        animate: bool,
+    ) -> Result<(SpriteVariant, SpriteFile)> {
+        let mut last_error = None;
+        for candidate in variant.fallbacks() {
+            if animate && let Ok(path) = self.fetch_animation(pokemon_id, *candidate) {
+                return Ok((*candidate, SpriteFile::Animated(path)));
+            }
+            match self.fetch_variant(pokemon_id, *candidate) {
+                Ok(path) => return Ok((*candidate, SpriteFile::Still(path))),
+                Err(e) => last_error = Some(e),
+            }
         }
-        Ok(())
+        Err(last_error
+            .unwrap_or_else(|| anyhow::anyhow!("No sprite for Pokemon ID {}", pokemon_id)))
+    }
+
+    /// キャッシュに無ければ取得する（prefetch 用）。取得したら true、既にあれば false
+    pub fn prefetch(&self, pokemon_id: u32) -> Result<bool> {
+        if self.get_sprite_path(pokemon_id).exists() {
+            return Ok(false);
+        }
+        self.fetch_sprite(pokemon_id)?;
+        Ok(true)
     }
 
     /// PokeAPIからスプライト画像をダウンロード
     pub fn fetch_sprite(&self, pokemon_id: u32) -> Result<PathBuf> {
-        let sprite_path = self.get_sprite_path(pokemon_id);
+        self.fetch_variant(pokemon_id, SpriteVariant::Default)
+    }
+
+    /// 指定バリアントをダウンロード（キャッシュにあればそれを使う）
+    pub fn fetch_variant(&self, pokemon_id: u32, variant: SpriteVariant) -> Result<PathBuf> {
+        let sprite_path = self.get_variant_path(pokemon_id, variant);
 
         if sprite_path.exists() {
             return Ok(sprite_path);
         }
 
-        let url = format!("{}/sprites/pokemon/{}.png", self.base_url, pokemon_id);
+        let url = format!(
+            "{}/sprites/pokemon/{}{}.png",
+            self.base_url,
+            variant.remote_dir(),
+            pokemon_id
+        );
+        self.download(&url, &sprite_path, pokemon_id, validate_png)?;
+        Ok(sprite_path)
+    }
+
+    /// アニメーション GIF のキャッシュパス（sprites/animated/ 配下）
+    pub fn get_animation_path(&self, pokemon_id: u32, variant: SpriteVariant) -> PathBuf {
+        let mut dir = self.cache_dir.join("animated");
+        if let Some(namespace) = variant.cache_namespace() {
+            dir = dir.join(namespace);
+        }
+        dir.join(format!("{}.gif", pokemon_id))
+    }
+
+    /// アニメーション GIF を ANIMATION_SOURCES の順に探してダウンロード
+    /// （キャッシュにあればそれを使う）。どこにも無ければエラー
+    pub fn fetch_animation(&self, pokemon_id: u32, variant: SpriteVariant) -> Result<PathBuf> {
+        let Some(variant_dir) = variant.animated_dir() else {
+            return Err(anyhow::anyhow!(
+                "No animation for {} sprites",
+                variant.label()
+            ));
+        };
+        let animation_path = self.get_animation_path(pokemon_id, variant);
+
+        if animation_path.exists() {
+            return Ok(animation_path);
+        }
+
+        let mut last_error = None;
+        for source in ANIMATION_SOURCES {
+            let url = format!(
+                "{}/sprites/pokemon/{}{}{}.gif",
+                self.base_url, source, variant_dir, pokemon_id
+            );
+            match self.download(&url, &animation_path, pokemon_id, validate_gif) {
+                Ok(()) => return Ok(animation_path),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| anyhow::anyhow!("No animation for Pokemon ID {}", pokemon_id)))
+    }
 
+    /// url を取得し、validate を通ったものだけを dest に置く
+    fn download(
+        &self,
+        url: &str,
+        dest: &Path,
+        pokemon_id: u32,
+        validate: fn(&[u8]) -> Result<()>,
+    ) -> Result<()> {
         let response = self
             .client
-            .get(&url)
+            .get(url)
             .send()
             .with_context(|| format!("Failed to fetch sprite for Pokemon ID {}", pokemon_id))?;
 
@@ -112,37 +428,60 @@ impl SpriteService {
 
         let content = response.bytes().context("Failed to read sprite data")?;
 
-        std::fs::write(&sprite_path, content)
-            .with_context(|| format!("Failed to save sprite to {}", sprite_path.display()))?;
+        // 404 ページや途中で切れた応答をキャッシュに入れると、exists() で
+        // 以後ずっとそれが使われる。画像として読めるものだけを置く
+        validate(&content)
+            .with_context(|| format!("Invalid sprite for Pokemon ID {}", pokemon_id))?;
 
-        Ok(sprite_path)
+        if let Some(dir) = dest.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+
+        // 直接書くと、途中で kill されたときに切れた画像が残る（cry.rs と同じ）
+        let extension = dest.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let tmp_path = dest.with_extension(format!("{}.{}.part", extension, std::process::id()));
+        std::fs::write(&tmp_path, &content)
+            .with_context(|| format!("Failed to save sprite to {}", tmp_path.display()))?;
+
+        std::fs::rename(&tmp_path, dest).with_context(|| {
+            format!(
+                "Failed to move {} into place at {}",
+                tmp_path.display(),
+                dest.display()
+            )
+        })?;
+
+        Ok(())
     }
 
-    /// スプライト画像をターミナルに表示
-    pub fn display_sprite(&self, sprite_path: &Path) -> Result<()> {
-        #[cfg(feature = "sprites")]
-        {
-            // Try viuer first - it handles terminal detection automatically
+    /// スプライト画像をターミナルに表示し、使った行数を返す。
+    /// viuer で描けなければブロックで描く
+    pub fn display_sprite(&self, sprite_path: &Path) -> Result<u16> {
+        if self.renderer.uses_graphics() {
             let img = image::open(sprite_path).with_context(|| {
                 format!("Failed to open sprite image: {}", sprite_path.display())
             })?;
-
-            let config = viuer::Config {
-                transparent: true,
-                absolute_offset: false,
-                ..Default::default()
-            };
-
-            match viuer::print(&img, &config) {
-                Ok(_) => {}
-                Err(e) => {
-                    // Fallback to text if viuer fails
-                    println!("🖼️  Sprite saved at: {}", sprite_path.display());
-                    println!("   (Terminal image display not available: {})", e);
-                }
+            if let Ok(height) = print_image(&img, &self.render_options) {
+                return Ok(height);
             }
         }
-        Ok(())
+        let lines = self.render_sprite(sprite_path)?;
+        print_lines(&lines)?;
+        Ok(lines.len() as u16)
+    }
+
+    /// スプライト画像を描画した行。印字はしない（横に並べる compare 用）
+    pub fn render_sprite(&self, sprite_path: &Path) -> Result<Vec<String>> {
+        let img = image::open(sprite_path)
+            .with_context(|| format!("Failed to open sprite image: {}", sprite_path.display()))?
+            .to_rgba8();
+        Ok(render::render(&img, &self.render_options))
+    }
+
+    /// 画像として最後まで読めるか（cache verify 用）
+    pub fn is_decodable(path: &Path) -> bool {
+        image::open(path).is_ok()
     }
 
     #[cfg(test)]
@@ -152,6 +491,8 @@ impl SpriteService {
             client,
             base_url,
             id_map: HashMap::new(),
+            render_options: RenderOptions::default(),
+            renderer: SpriteRenderer::Blocks,
         }
     }
 
@@ -162,10 +503,193 @@ impl SpriteService {
             client: Client::new(),
             base_url: "test://mock".to_string(),
             id_map,
+            render_options: RenderOptions::default(),
+            renderer: SpriteRenderer::Blocks,
         }
     }
 }
 
+/// viuer で画像をカーソル位置に描き、使った行数を返す。サイズ指定は render と同じく文字数
+#[cfg(feature = "sprites")]
+fn print_image(img: &image::DynamicImage, options: &RenderOptions) -> Result<u16> {
+    let config = viuer::Config {
+        transparent: true,
+        absolute_offset: false,
+        width: options.width,
+        height: options.height,
+        ..Default::default()
+    };
+    let (_, height) = viuer::print(img, &config)?;
+    Ok(height as u16)
+}
+
+/// 描画済みの行をカーソル位置から書く。raw mode でも崩れないよう改行は \r\n
+#[cfg(feature = "sprites")]
+fn print_lines(lines: &[String]) -> Result<()> {
+    use std::io::Write;
+
+    let mut stdout = std::io::stdout().lock();
+    for line in lines {
+        write!(stdout, "{}\r\n", line)?;
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+/// アニメーション GIF のフレームと、それを同じ位置に描き直していく状態
+#[cfg(feature = "sprites")]
+pub struct SpriteAnimation {
+    /// 描画済みのフレームと表示時間
+    frames: Vec<(Vec<String>, Duration)>,
+    current: usize,
+    /// 1フレームの行数（全フレーム共通）
+    height: u16,
+    /// フレームの下に印字された行数。描き直すときはその分も戻る
+    lines_below: u16,
+}
+
+#[cfg(feature = "sprites")]
+impl SpriteAnimation {
+    /// GIF を全フレームデコードして描画しておく。透明な縁は全フレーム共通で切り落とす
+    pub fn open(path: &Path, options: &RenderOptions) -> Result<Self> {
+        use image::AnimationDecoder;
+        use image::codecs::gif::GifDecoder;
+
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open animation: {}", path.display()))?;
+        let decoder = GifDecoder::new(std::io::BufReader::new(file))
+            .with_context(|| format!("Failed to decode animation: {}", path.display()))?;
+        let frames = decoder
+            .into_frames()
+            .collect_frames()
+            .with_context(|| format!("Failed to decode animation: {}", path.display()))?;
+        let bounds = frames
+            .iter()
+            .filter_map(|frame| render::opaque_bounds(frame.buffer()))
+            .reduce(render::Bounds::union)
+            .with_context(|| format!("No visible frames in {}", path.display()))?;
+
+        let frames: Vec<_> = frames
+            .into_iter()
+            .map(|frame| {
+                let lines = render::render_region(frame.buffer(), bounds, options);
+                (lines, frame_delay(frame.delay()))
+            })
+            .collect();
+        let height = frames[0].0.len() as u16;
+
+        Ok(Self {
+            frames,
+            current: 0,
+            height,
+            lines_below: 0,
+        })
+    }
+
+    /// 最初のフレームをカーソル位置に描く
+    pub fn draw_first(&mut self) -> Result<()> {
+        self.current = 0;
+        print_lines(&self.frames[0].0)
+    }
+
+    /// 画像の下に lines 行印字したことを伝える
+    pub fn add_lines_below(&mut self, lines: usize) {
+        self.lines_below = self.lines_below.saturating_add(lines as u16);
+    }
+
+    /// フレームを進めながらキー入力を待ち、押されたキーを返す。
+    /// 呼び出し側で raw mode にしておくこと
+    pub fn next_key(&mut self) -> Result<KeyEvent> {
+        if self.frames.len() < 2 {
+            return read_key();
+        }
+
+        let mut deadline = Instant::now() + self.frames[self.current].1;
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            if event::poll(timeout)? {
+                if let Event::Key(key) = event::read()?
+                    && key.kind == KeyEventKind::Press
+                {
+                    return Ok(key);
+                }
+                continue;
+            }
+            self.current = (self.current + 1) % self.frames.len();
+            self.redraw()?;
+            deadline = Instant::now() + self.frames[self.current].1;
+        }
+    }
+
+    /// 前のフレームの位置へ戻って今のフレームを描き、カーソルを元の位置へ戻す
+    fn redraw(&self) -> Result<()> {
+        use crossterm::cursor::{MoveToColumn, MoveToNextLine, MoveUp};
+        use crossterm::execute;
+
+        let mut stdout = std::io::stdout();
+        execute!(
+            stdout,
+            MoveUp(self.height + self.lines_below),
+            MoveToColumn(0)
+        )?;
+        print_lines(&self.frames[self.current].0)?;
+        if self.lines_below > 0 {
+            execute!(stdout, MoveToNextLine(self.lines_below))?;
+        }
+        Ok(())
+    }
+}
+
+/// キーが押されるまで待つ（Press のみ。Repeat / Release は無視する）
+#[cfg(feature = "sprites")]
+pub fn read_key() -> Result<KeyEvent> {
+    loop {
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            return Ok(key);
+        }
+    }
+}
+
+/// GIF のフレームの表示時間。0 や極端に短い値はブラウザと同じく 100ms として扱う
+#[cfg(feature = "sprites")]
+fn frame_delay(delay: image::Delay) -> Duration {
+    let (numer, denom) = delay.numer_denom_ms();
+    let ms = u64::from(numer) / u64::from(denom.max(1));
+    if ms < 20 {
+        Duration::from_millis(100)
+    } else {
+        Duration::from_millis(ms)
+    }
+}
+
+/// PNG のシグネチャ
+#[cfg(feature = "sprites")]
+const PNG_MAGIC: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// PNG のシグネチャで始まり、最後までデコードできるか
+#[cfg(feature = "sprites")]
+fn validate_png(bytes: &[u8]) -> Result<()> {
+    if !bytes.starts_with(PNG_MAGIC) {
+        return Err(anyhow::anyhow!("Not a PNG file"));
+    }
+    image::load_from_memory_with_format(bytes, image::ImageFormat::Png)
+        .context("Failed to decode PNG")?;
+    Ok(())
+}
+
+/// GIF のシグネチャで始まり、1フレーム目をデコードできるか
+#[cfg(feature = "sprites")]
+fn validate_gif(bytes: &[u8]) -> Result<()> {
+    if !bytes.starts_with(b"GIF87a") && !bytes.starts_with(b"GIF89a") {
+        return Err(anyhow::anyhow!("Not a GIF file"));
+    }
+    image::load_from_memory_with_format(bytes, image::ImageFormat::Gif)
+        .context("Failed to decode GIF")?;
+    Ok(())
+}
+
 #[cfg(test)]
 #[cfg(feature = "sprites")]
 mod tests {
@@ -191,6 +715,8 @@ mod tests {
             client: Client::new(),
             base_url: "http://dummy.example.com".to_string(),
             id_map: HashMap::new(),
+            render_options: RenderOptions::default(),
+            renderer: SpriteRenderer::Blocks,
         };
 
         let path = service.get_sprite_path(25);
@@ -208,6 +734,8 @@ mod tests {
             client: Client::new(),
             base_url: "http://dummy.example.com".to_string(),
             id_map: HashMap::new(),
+            render_options: RenderOptions::default(),
+            renderer: SpriteRenderer::Blocks,
         };
 
         let sprite_path = service.get_sprite_path(25);
@@ -229,6 +757,8 @@ mod tests {
             client: Client::new(),
             base_url: "http://dummy.example.com".to_string(),
             id_map: HashMap::new(),
+            render_options: RenderOptions::default(),
+            renderer: SpriteRenderer::Blocks,
         };
 
         // Test through get_sprite_path which uses cache_dir
@@ -244,6 +774,8 @@ mod tests {
             client: Client::new(),
             base_url: "http://dummy.example.com".to_string(),
             id_map: HashMap::new(),
+            render_options: RenderOptions::default(),
+            renderer: SpriteRenderer::Blocks,
         };
 
         // Create a cached sprite
@@ -268,6 +800,8 @@ mod tests {
             client: Client::new(),
             base_url: "http://dummy.example.com".to_string(),
             id_map: HashMap::new(),
+            render_options: RenderOptions::default(),
+            renderer: SpriteRenderer::Blocks,
         };
 
         let non_existent = temp_dir.path().join("non_existent.png");
@@ -282,15 +816,8 @@ mod tests {
         // Start a mock server
         let server = MockServer::start();
 
-        // Create a mock sprite image (1x1 PNG)
-        let mock_png = vec![
-            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, // PNG signature
-            0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52, // IHDR chunk
-            0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x37,
-            0x6E, 0xF9, 0x24, 0x00, 0x00, 0x00, 0x0A, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x62,
-            0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01, 0xE5, 0x27, 0xDE, 0xFC, 0x00, 0x00, 0x00,
-            0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
-        ];
+        // Create a mock sprite image (1x1 PNG)。取得時に検証されるので本物の PNG を使う
+        let mock_png = tiny_png();
 
         // Create a mock for Pokemon sprite
         let mock = server.mock(|when, then| {
@@ -353,6 +880,320 @@ mod tests {
         assert!(!service.get_sprite_path(9999).exists());
     }
 
+    /// 1x1 の PNG（image で生成）
+    fn tiny_png() -> Vec<u8> {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image::RgbaImage::new(1, 1)
+            .write_to(&mut bytes, image::ImageOutputFormat::Png)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_validate_png() {
+        let png = tiny_png();
+        assert!(validate_png(&png).is_ok());
+        // シグネチャが無い
+        assert!(validate_png(b"<html>Not Found</html>").is_err());
+        // 途中で切れている
+        assert!(validate_png(&png[..png.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn test_fetch_sprite_rejects_invalid_content() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let png = tiny_png();
+        let truncated = png[..png.len() - 10].to_vec();
+        server.mock(|when, then| {
+            when.method(GET).path("/sprites/pokemon/25.png");
+            then.status(200)
+                .header("content-type", "image/png")
+                .body(&truncated);
+        });
+
+        let temp_dir = tempdir().unwrap();
+        let service = SpriteService::with_base_url(
+            temp_dir.path().to_path_buf(),
+            Client::new(),
+            server.url(""),
+        );
+
+        assert!(service.fetch_sprite(25).is_err());
+        // 本体も .part も残さない
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_fetch_sprite_leaves_no_partial_file_behind() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let png = tiny_png();
+        server.mock(|when, then| {
+            when.method(GET).path("/sprites/pokemon/25.png");
+            then.status(200).body(&png);
+        });
+
+        let temp_dir = tempdir().unwrap();
+        let service = SpriteService::with_base_url(
+            temp_dir.path().to_path_buf(),
+            Client::new(),
+            server.url(""),
+        );
+
+        let sprite_path = service.fetch_sprite(25).unwrap();
+        let files: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(files, vec![sprite_path]);
+    }
+
+    #[test]
+    fn test_variant_paths_are_namespaced() {
+        let temp_dir = tempdir().unwrap();
+        let service = SpriteService::for_test(temp_dir.path().to_path_buf(), HashMap::new());
+
+        assert_eq!(
+            service.get_variant_path(25, SpriteVariant::Default),
+            temp_dir.path().join("25.png")
+        );
+        assert_eq!(
+            service.get_variant_path(25, SpriteVariant::BackShiny),
+            temp_dir.path().join("back-shiny").join("25.png")
+        );
+        assert_eq!(
+            service.get_variant_path(25, SpriteVariant::OfficialArtwork),
+            temp_dir.path().join("official-artwork").join("25.png")
+        );
+    }
+
+    #[test]
+    fn test_variant_next_cycles() {
+        assert_eq!(SpriteVariant::Default.next(), SpriteVariant::Shiny);
+        assert_eq!(SpriteVariant::Female.next(), SpriteVariant::Default);
+        // 全バリアントの代わりの最後は通常
+        for variant in SpriteVariant::ALL {
+            assert_eq!(variant.fallbacks()[0], variant);
+            assert_eq!(variant.fallbacks().last(), Some(&SpriteVariant::Default));
+        }
+    }
+
+    #[test]
+    fn test_fetch_variant_uses_remote_dir() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let png = tiny_png();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/sprites/pokemon/other/official-artwork/25.png");
+            then.status(200).body(&png);
+        });
+
+        let temp_dir = tempdir().unwrap();
+        let service = SpriteService::with_base_url(
+            temp_dir.path().to_path_buf(),
+            Client::new(),
+            server.url(""),
+        );
+
+        let path = service
+            .fetch_variant(25, SpriteVariant::OfficialArtwork)
+            .unwrap();
+        assert_eq!(
+            path,
+            temp_dir.path().join("official-artwork").join("25.png")
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_fetch_with_fallback_uses_next_available() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let png = tiny_png();
+        // 後ろ姿の色違いも後ろ姿も無く、色違いはある
+        server.mock(|when, then| {
+            when.method(GET).path("/sprites/pokemon/back/shiny/906.png");
+            then.status(404);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/sprites/pokemon/back/906.png");
+            then.status(404);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/sprites/pokemon/shiny/906.png");
+            then.status(200).body(&png);
+        });
+
+        let temp_dir = tempdir().unwrap();
+        let service = SpriteService::with_base_url(
+            temp_dir.path().to_path_buf(),
+            Client::new(),
+            server.url(""),
+        );
+
+        let (shown, file) = service
+            .fetch_with_fallback(906, SpriteVariant::BackShiny, false)
+            .unwrap();
+        assert_eq!(shown, SpriteVariant::Shiny);
+        assert_eq!(
+            file,
+            SpriteFile::Still(temp_dir.path().join("shiny").join("906.png"))
+        );
+    }
+
+    fn tiny_gif(frames: usize) -> Vec<u8> {
+        use image::codecs::gif::GifEncoder;
+        use image::{Delay, Frame};
+
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut bytes);
+            for i in 0..frames {
+                let pixel = image::Rgba([i as u8 * 100, 0, 0, 255]);
+                let buffer = image::RgbaImage::from_pixel(2, 2, pixel);
+                encoder
+                    .encode_frame(Frame::from_parts(
+                        buffer,
+                        0,
+                        0,
+                        Delay::from_numer_denom_ms(50, 1),
+                    ))
+                    .unwrap();
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_frame_delay() {
+        use image::Delay;
+
+        assert_eq!(
+            frame_delay(Delay::from_numer_denom_ms(50, 1)),
+            Duration::from_millis(50)
+        );
+        // 0 は既定値に
+        assert_eq!(
+            frame_delay(Delay::from_numer_denom_ms(0, 1)),
+            Duration::from_millis(100)
+        );
+    }
+
+    #[test]
+    fn test_animation_open_reads_all_frames() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("25.gif");
+        fs::write(&path, tiny_gif(3)).unwrap();
+
+        let animation = SpriteAnimation::open(&path, &RenderOptions::default()).unwrap();
+        assert_eq!(animation.frames.len(), 3);
+        assert_eq!(animation.frames[0].1, Duration::from_millis(50));
+        // 2×2 ピクセルは1行
+        assert_eq!(animation.height, 1);
+        assert!(animation.frames.iter().all(|(lines, _)| lines.len() == 1));
+    }
+
+    #[test]
+    fn test_validate_gif() {
+        assert!(validate_gif(&tiny_gif(1)).is_ok());
+        assert!(validate_gif(&tiny_png()).is_err());
+    }
+
+    #[test]
+    fn test_fetch_animation_tries_sources_in_order() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let gif = tiny_gif(2);
+        // 第5世代の動くスプライトには無く、Showdown にはある
+        let black_white = server.mock(|when, then| {
+            when.method(GET)
+                .path("/sprites/pokemon/versions/generation-v/black-white/animated/shiny/906.gif");
+            then.status(404);
+        });
+        let showdown = server.mock(|when, then| {
+            when.method(GET)
+                .path("/sprites/pokemon/other/showdown/shiny/906.gif");
+            then.status(200).body(&gif);
+        });
+
+        let temp_dir = tempdir().unwrap();
+        let service = SpriteService::with_base_url(
+            temp_dir.path().to_path_buf(),
+            Client::new(),
+            server.url(""),
+        );
+
+        let path = service.fetch_animation(906, SpriteVariant::Shiny).unwrap();
+        assert_eq!(
+            path,
+            temp_dir
+                .path()
+                .join("animated")
+                .join("shiny")
+                .join("906.gif")
+        );
+        black_white.assert();
+        showdown.assert();
+
+        // 2回目はキャッシュから
+        service.fetch_animation(906, SpriteVariant::Shiny).unwrap();
+        showdown.assert_hits(1);
+    }
+
+    #[test]
+    fn test_fetch_with_fallback_prefers_still_of_requested_variant() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        let png = tiny_png();
+        let gif = tiny_gif(2);
+        // 公式アートワークに動くものは無いので、HOME のアニメーションではなく静止画を使う
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/sprites/pokemon/other/official-artwork/25.png");
+            then.status(200).body(&png);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path_contains("/animated/25.gif");
+            then.status(200).body(&gif);
+        });
+
+        let temp_dir = tempdir().unwrap();
+        let service = SpriteService::with_base_url(
+            temp_dir.path().to_path_buf(),
+            Client::new(),
+            server.url(""),
+        );
+
+        let (shown, file) = service
+            .fetch_with_fallback(25, SpriteVariant::OfficialArtwork, true)
+            .unwrap();
+        assert_eq!(shown, SpriteVariant::OfficialArtwork);
+        assert!(matches!(file, SpriteFile::Still(_)));
+
+        let (shown, file) = service
+            .fetch_with_fallback(25, SpriteVariant::Default, true)
+            .unwrap();
+        assert_eq!(shown, SpriteVariant::Default);
+        assert_eq!(
+            file,
+            SpriteFile::Animated(temp_dir.path().join("animated").join("25.gif"))
+        );
+    }
+
+    #[test]
+    fn test_renderer_choice() {
+        assert!(SpriteRenderer::Graphics.uses_graphics());
+        assert!(!SpriteRenderer::Blocks.uses_graphics());
+    }
+
     #[test]
     fn test_get_pokemon_id() {
         let temp_dir = tempdir().unwrap();
@@ -0,0 +1,249 @@
+//! タイプ相性表（第6世代以降）。防御側のタイプの組み合わせから、
+//! 各攻撃タイプの倍率（弱点 ×4/×2・耐性 ×½/×¼・無効）を求める。
+//!
+//! 相性は固定なので API を叩かず表を持つ。辞書の types（英語スラッグ）だけで
+//! 計算できるため、オフラインでも使える。
+
+use crate::pokemon_type::TYPE_SLUGS;
+
+/// 等倍以外の組み合わせ（攻撃タイプ, 防御タイプ, 倍率）。載っていなければ等倍
+const CHART: &[(&str, &str, f32)] = &[
+    ("normal", "rock", 0.5),
+    ("normal", "ghost", 0.0),
+    ("normal", "steel", 0.5),
+    ("fire", "fire", 0.5),
+    ("fire", "water", 0.5),
+    ("fire", "grass", 2.0),
+    ("fire", "ice", 2.0),
+    ("fire", "bug", 2.0),
+    ("fire", "rock", 0.5),
+    ("fire", "dragon", 0.5),
+    ("fire", "steel", 2.0),
+    ("water", "fire", 2.0),
+    ("water", "water", 0.5),
+    ("water", "grass", 0.5),
+    ("water", "ground", 2.0),
+    ("water", "rock", 2.0),
+    ("water", "dragon", 0.5),
+    ("electric", "water", 2.0),
+    ("electric", "electric", 0.5),
+    ("electric", "grass", 0.5),
+    ("electric", "ground", 0.0),
+    ("electric", "flying", 2.0),
+    ("electric", "dragon", 0.5),
+    ("grass", "fire", 0.5),
+    ("grass", "water", 2.0),
+    ("grass", "grass", 0.5),
+    ("grass", "poison", 0.5),
+    ("grass", "ground", 2.0),
+    ("grass", "flying", 0.5),
+    ("grass", "bug", 0.5),
+    ("grass", "rock", 2.0),
+    ("grass", "dragon", 0.5),
+    ("grass", "steel", 0.5),
+    ("ice", "fire", 0.5),
+    ("ice", "water", 0.5),
+    ("ice", "grass", 2.0),
+    ("ice", "ice", 0.5),
+    ("ice", "ground", 2.0),
+    ("ice", "flying", 2.0),
+    ("ice", "dragon", 2.0),
+    ("ice", "steel", 0.5),
+    ("fighting", "normal", 2.0),
+    ("fighting", "ice", 2.0),
+    ("fighting", "poison", 0.5),
+    ("fighting", "flying", 0.5),
+    ("fighting", "psychic", 0.5),
+    ("fighting", "bug", 0.5),
+    ("fighting", "rock", 2.0),
+    ("fighting", "ghost", 0.0),
+    ("fighting", "dark", 2.0),
+    ("fighting", "steel", 2.0),
+    ("fighting", "fairy", 0.5),
+    ("poison", "grass", 2.0),
+    ("poison", "poison", 0.5),
+    ("poison", "ground", 0.5),
+    ("poison", "rock", 0.5),
+    ("poison", "ghost", 0.5),
+    ("poison", "steel", 0.0),
+    ("poison", "fairy", 2.0),
+    ("ground", "fire", 2.0),
+    ("ground", "electric", 2.0),
+    ("ground", "grass", 0.5),
+    ("ground", "poison", 2.0),
+    ("ground", "flying", 0.0),
+    ("ground", "bug", 0.5),
+    ("ground", "rock", 2.0),
+    ("ground", "steel", 2.0),
+    ("flying", "electric", 0.5),
+    ("flying", "grass", 2.0),
+    ("flying", "fighting", 2.0),
+    ("flying", "bug", 2.0),
+    ("flying", "rock", 0.5),
+    ("flying", "steel", 0.5),
+    ("psychic", "fighting", 2.0),
+    ("psychic", "poison", 2.0),
+    ("psychic", "psychic", 0.5),
+    ("psychic", "dark", 0.0),
+    ("psychic", "steel", 0.5),
+    ("bug", "fire", 0.5),
+    ("bug", "grass", 2.0),
+    ("bug", "fighting", 0.5),
+    ("bug", "poison", 0.5),
+    ("bug", "flying", 0.5),
+    ("bug", "psychic", 2.0),
+    ("bug", "ghost", 0.5),
+    ("bug", "dark", 2.0),
+    ("bug", "steel", 0.5),
+    ("bug", "fairy", 0.5),
+    ("rock", "fire", 2.0),
+    ("rock", "ice", 2.0),
+    ("rock", "fighting", 0.5),
+    ("rock", "ground", 0.5),
+    ("rock", "flying", 2.0),
+    ("rock", "bug", 2.0),
+    ("rock", "steel", 0.5),
+    ("ghost", "normal", 0.0),
+    ("ghost", "psychic", 2.0),
+    ("ghost", "ghost", 2.0),
+    ("ghost", "dark", 0.5),
+    ("dragon", "dragon", 2.0),
+    ("dragon", "steel", 0.5),
+    ("dragon", "fairy", 0.0),
+    ("dark", "fighting", 0.5),
+    ("dark", "psychic", 2.0),
+    ("dark", "ghost", 2.0),
+    ("dark", "dark", 0.5),
+    ("dark", "fairy", 0.5),
+    ("steel", "fire", 0.5),
+    ("steel", "water", 0.5),
+    ("steel", "electric", 0.5),
+    ("steel", "ice", 2.0),
+    ("steel", "rock", 2.0),
+    ("steel", "steel", 0.5),
+    ("steel", "fairy", 2.0),
+    ("fairy", "fire", 0.5),
+    ("fairy", "fighting", 2.0),
+    ("fairy", "poison", 0.5),
+    ("fairy", "dragon", 2.0),
+    ("fairy", "dark", 2.0),
+    ("fairy", "steel", 0.5),
+];
+
+/// 1対1の倍率
+pub fn multiplier(attacking: &str, defending: &str) -> f32 {
+    CHART
+        .iter()
+        .find(|(a, d, _)| *a == attacking && *d == defending)
+        .map_or(1.0, |(_, _, m)| *m)
+}
+
+/// 防御側から見た相性。各欄は攻撃タイプのスラッグで、TYPE_SLUGS の順
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Matchups {
+    /// ×4
+    pub quadruple: Vec<&'static str>,
+    /// ×2
+    pub double: Vec<&'static str>,
+    /// ×½
+    pub half: Vec<&'static str>,
+    /// ×¼
+    pub quarter: Vec<&'static str>,
+    /// ×0
+    pub immune: Vec<&'static str>,
+}
+
+impl Matchups {
+    pub fn is_empty(&self) -> bool {
+        self.quadruple.is_empty()
+            && self.double.is_empty()
+            && self.half.is_empty()
+            && self.quarter.is_empty()
+            && self.immune.is_empty()
+    }
+}
+
+/// 防御側のタイプ（英語スラッグ）の組み合わせに対する相性。
+/// 未知のスラッグは無視し、タイプが無ければ空
+pub fn matchups<S: AsRef<str>>(defending: &[S]) -> Matchups {
+    let defending: Vec<&str> = defending
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|slug| TYPE_SLUGS.contains(slug))
+        .collect();
+    let mut result = Matchups::default();
+    if defending.is_empty() {
+        return result;
+    }
+
+    for attacking in TYPE_SLUGS {
+        let total: f32 = defending
+            .iter()
+            .map(|defending| multiplier(attacking, defending))
+            .product();
+        let bucket = if total == 0.0 {
+            &mut result.immune
+        } else if total >= 4.0 {
+            &mut result.quadruple
+        } else if total >= 2.0 {
+            &mut result.double
+        } else if total <= 0.25 {
+            &mut result.quarter
+        } else if total <= 0.5 {
+            &mut result.half
+        } else {
+            continue;
+        };
+        bucket.push(attacking);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multiplier() {
+        assert_eq!(multiplier("water", "fire"), 2.0);
+        assert_eq!(multiplier("fire", "water"), 0.5);
+        assert_eq!(multiplier("ground", "flying"), 0.0);
+        assert_eq!(multiplier("normal", "fire"), 1.0);
+    }
+
+    #[test]
+    fn test_matchups_dual_type() {
+        // リザードン（ほのお・ひこう）
+        let result = matchups(&["fire", "flying"]);
+        assert_eq!(result.quadruple, ["rock"]);
+        assert_eq!(result.double, ["water", "electric"]);
+        assert_eq!(result.half, ["fire", "fighting", "steel", "fairy"]);
+        assert_eq!(result.quarter, ["grass", "bug"]);
+        assert_eq!(result.immune, ["ground"]);
+    }
+
+    #[test]
+    fn test_matchups_single_type() {
+        // ゲンガー系（ゴースト単）
+        let result = matchups(&["ghost"]);
+        assert_eq!(result.double, ["ghost", "dark"]);
+        assert_eq!(result.half, ["poison", "bug"]);
+        assert_eq!(result.immune, ["normal", "fighting"]);
+        assert!(result.quadruple.is_empty());
+    }
+
+    #[test]
+    fn test_matchups_without_types() {
+        assert!(matchups::<&str>(&[]).is_empty());
+        assert!(matchups(&["stellar"]).is_empty());
+    }
+
+    #[test]
+    fn test_chart_uses_known_types() {
+        assert!(
+            CHART
+                .iter()
+                .all(|(a, d, _)| TYPE_SLUGS.contains(a) && TYPE_SLUGS.contains(d))
+        );
+    }
+}
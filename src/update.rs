@@ -5,6 +5,7 @@ use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
 
+use crate::crawler::PokeApiCrawler;
 use crate::data::DataLoader;
 use crate::models::NameDictionary;
 
@@ -105,6 +106,41 @@ impl UpdateService {
         Ok(())
     }
 
+    /// PokéAPI を直接クロールして辞書を生成・更新（CI配布が使えないときの非常口）
+    ///
+    /// # Arguments
+    /// * `api_base` - PokéAPI のベースURL（省略時は pokeapi.co）
+    /// * `dry_run` - trueの場合、生成と検証のみ実行
+    pub fn update_online(&self, api_base: Option<String>, dry_run: bool) -> Result<()> {
+        let crawler = match api_base {
+            Some(base) => PokeApiCrawler::with_base_url(base)?,
+            None => PokeApiCrawler::new()?,
+        };
+        self.save_crawled(&crawler, dry_run)
+    }
+
+    fn save_crawled(&self, crawler: &PokeApiCrawler, dry_run: bool) -> Result<()> {
+        let dictionary = crawler.crawl().context("Failed to crawl PokéAPI")?;
+
+        eprintln!("Generated {} entries", dictionary.count);
+
+        dictionary
+            .validate()
+            .map_err(|e| anyhow::anyhow!("Validation failed: {}", e))?;
+
+        if dry_run {
+            eprintln!("Dry run mode: not saving the file");
+            return Ok(());
+        }
+
+        let content =
+            serde_json::to_vec_pretty(&dictionary).context("Failed to serialize dictionary")?;
+        self.save_atomic(&content)?;
+
+        eprintln!("Successfully updated names.json");
+        Ok(())
+    }
+
     fn save_atomic(&self, content: &[u8]) -> Result<()> {
         self.data_loader.ensure_data_dir()?;
 
@@ -183,6 +219,7 @@ mod tests {
                 en: "Pikachu".to_string(),
                 id: None,
                 types: vec![],
+                ..Default::default()
             }],
         };
 
@@ -198,6 +235,46 @@ mod tests {
         assert_eq!(saved_dict.entries[0].ja, "ピカチュウ");
     }
 
+    #[test]
+    fn test_save_crawled_dry_run_does_not_write() {
+        use httpmock::prelude::*;
+
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon-species");
+            then.status(200).json_body(serde_json::json!({
+                "count": 1,
+                "results": [{"name": "pikachu", "url": "https://pokeapi.co/api/v2/pokemon-species/25/"}]
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pokemon-species/25");
+            then.status(200).json_body(serde_json::json!({
+                "id": 25,
+                "name": "pikachu",
+                "names": [
+                    {"name": "ピカチュウ", "language": {"name": "ja-Hrkt"}},
+                    {"name": "Pikachu", "language": {"name": "en"}}
+                ],
+                "varieties": []
+            }));
+        });
+
+        let temp_dir = tempdir().unwrap();
+        let dict_path = temp_dir.path().join("names.json");
+        let service = UpdateService::with_path(dict_path.clone()).unwrap();
+        let crawler = PokeApiCrawler::for_test(server.url(""));
+
+        service.save_crawled(&crawler, true).unwrap();
+        assert!(!dict_path.exists());
+
+        service.save_crawled(&crawler, false).unwrap();
+        let saved: NameDictionary = serde_json::from_slice(&fs::read(&dict_path).unwrap()).unwrap();
+        assert_eq!(saved.count, 1);
+        assert_eq!(saved.entries[0].en, "Pikachu");
+        assert_eq!(saved.entries[0].id, Some(25));
+    }
+
     #[test]
     fn test_default_url_constant() {
         assert!(DEFAULT_DOWNLOAD_URL.starts_with("https://"));